@@ -0,0 +1,61 @@
+use std::{env, fs, path::Path};
+
+/// The number of entries of each generated invocation.
+const ENTRIES: usize = 600;
+
+fn main() {
+    // a mix of kinds and sources, sharing a few module roots
+    let entries = (0..ENTRIES).map(|index| match index % 6 {
+        0 => format!("function(function_{index}) -> implementations::function"),
+        1 => format!("attribute(attribute_{index}) -> mod implementations::nested::attribute"),
+        2 => format!("derive(Derive{index}) -> use implementations::derive"),
+        3 => format!(
+            "#[cfg(not(any()))] function(conditional_{index}) -> implementations::nested::function"
+        ),
+        4 => format!("function(literal_{index}) -> try \"literal.rs\"::function"),
+        _ => format!(
+            "implementations {{ function(grouped_{index}) -> function, \
+             attribute(grouped_attribute_{index}) -> nested::attribute }}"
+        ),
+    });
+
+    // every entry gated with a cfg of its own, none of them declaring the shared module root
+    // unconditionally
+    println!(
+        "cargo:rustc-check-cfg=cfg(gated, values({}))",
+        (0..ENTRIES)
+            .map(|index| format!("\"{index}\""))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    let gated = (0..ENTRIES).map(|index| {
+        format!("#[cfg(not(gated = \"{index}\"))] function(gated_{index}) -> gated::function")
+    });
+
+    // `lib.rs` invokes these, so that `literal.rs` and the module roots are looked up next to it
+    // rather than in `OUT_DIR`
+    let invocations = [invocation("entries", entries), invocation("gated_entries", gated)];
+
+    fs::write(
+        Path::new(&env::var("OUT_DIR").unwrap()).join("entries.rs"),
+        invocations.concat(),
+    )
+    .unwrap();
+}
+
+fn invocation(name: &str, entries: impl Iterator<Item = String>) -> String {
+    let entries = entries
+        .map(|entry| format!("            {entry},\n"))
+        .collect::<String>();
+
+    format!(
+        r#"
+macro_rules! {name} {{
+    () => {{
+        include_proc_macro::macros!(
+{entries}        );
+    }};
+}}
+"#
+    )
+}
@@ -0,0 +1,5 @@
+use proc_macro::TokenStream;
+
+pub fn function(input: TokenStream) -> TokenStream {
+    input
+}
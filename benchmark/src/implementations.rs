@@ -0,0 +1,11 @@
+use proc_macro::TokenStream;
+
+pub mod nested;
+
+pub fn function(input: TokenStream) -> TokenStream {
+    input
+}
+
+pub fn derive(_input: TokenStream) -> TokenStream {
+    TokenStream::new()
+}
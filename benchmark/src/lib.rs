@@ -0,0 +1,10 @@
+//! `macros!` invocations with hundreds of entries (generated by the build script), which have to
+//! compile without raising the recursion limit, and in a time that grows linearly with the
+//! number of entries, as the frontend expands them. `cargo build -p benchmark --timings`
+//! reports how long it takes.
+//!
+//! The first one mixes kinds and sources, the second one gates every entry with a cfg of its own.
+
+include!(concat!(env!("OUT_DIR"), "/entries.rs"));
+entries!();
+gated_entries!();
@@ -0,0 +1,43 @@
+use std::{env, fs, path::Path};
+
+#[path = "build/matrix.rs"]
+mod matrix;
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let version = env::var("CARGO_PKG_VERSION").unwrap();
+    let implementation = format!(
+        r#"
+pub fn version(_input: proc_macro::TokenStream) -> proc_macro::TokenStream {{
+    "\"{version}\"".parse().unwrap()
+}}
+"#
+    );
+
+    fs::write(Path::new(&out_dir).join("version.rs"), implementation).unwrap();
+
+    // a macro rather than the invocation itself, so that the paths of the entries are relative
+    // to where it's invoked
+    let frontend = env::var_os("CARGO_FEATURE_FRONTEND").is_some();
+    let entries = matrix::entries(frontend)
+        .map(|entry| {
+            format!(
+                "            /// Expands to the source of the implementation (`{0}`).\n            {0},\n",
+                entry.entry()
+            )
+        })
+        .collect::<String>();
+    let matrix = format!(
+        r#"
+/// Registers the matrix of `macros!` entries (see `build/matrix.rs`).
+macro_rules! matrix {{
+    () => {{
+        include_proc_macro::macros!(
+{entries}        );
+    }};
+}}
+"#
+    );
+
+    fs::write(Path::new(&out_dir).join("matrix.rs"), matrix).unwrap();
+}
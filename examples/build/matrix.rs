@@ -0,0 +1,92 @@
+//! The matrix of `macros!` entries, one per kind, naming and implementation source, registered
+//! by `examples` and tested by `integration_test` (both generated by their build scripts).
+
+/// The kinds of macros.
+const KINDS: [&str; 3] = ["function", "attribute", "derive"];
+
+/// How the macro is named: after the implementation, explicitly, or explicitly along with
+/// helper attributes (derives only).
+const NAMINGS: [&str; 3] = ["implicit", "named", "helpers"];
+
+/// The implementation sources, where `{}` stands for the implementation function.
+const SOURCES: [(&str, &str); 7] = [
+    ("bare", "{}"),
+    ("path", "matrix::{}"),
+    ("declared", "mod matrix::declared::{}"),
+    ("imported", "use matrix::imported::{}"),
+    ("nested", "matrix::nested::deeper::{}"),
+    ("literal", "\"matrix/literal.rs\"::{}"),
+    ("relative", "@\"src/matrix/relative.rs\"::{}"),
+];
+
+pub struct Entry {
+    pub kind: &'static str,
+    pub naming: &'static str,
+    pub source: &'static str,
+    path: &'static str,
+}
+
+impl Entry {
+    /// The implementation function, e.g. `path_derive`.
+    pub fn implementation(&self) -> String {
+        format!("{}_{}", self.source, self.kind)
+    }
+
+    /// The name of the macro, e.g. `path_function`, `named_path_function` or `NamedPathDerive`.
+    pub fn name(&self) -> String {
+        match (self.naming, self.kind) {
+            ("implicit", _) => self.implementation(),
+            (naming, "derive") => {
+                format!("{}{}Derive", capitalize(naming), capitalize(self.source))
+            },
+            (naming, kind) => format!("{naming}_{}_{kind}", self.source),
+        }
+    }
+
+    /// The `macros!` entry.
+    pub fn entry(&self) -> String {
+        let path = self.path.replace("{}", &self.implementation());
+        match self.naming {
+            "implicit" => format!("{} -> {path}", self.kind),
+            "named" => format!("{}({}) -> {path}", self.kind, self.name()),
+            _ => format!(
+                "{}({}, attributes(matrix)) -> {path}",
+                self.kind,
+                self.name()
+            ),
+        }
+    }
+}
+
+/// The entries that can be registered with or without the frontend of `include_proc_macro`.
+pub fn entries(frontend: bool) -> impl Iterator<Item = Entry> {
+    SOURCES.into_iter().flat_map(move |(source, path)| {
+        KINDS.into_iter().flat_map(move |kind| {
+            NAMINGS
+                .into_iter()
+                .map(move |naming| Entry {
+                    kind,
+                    naming,
+                    source,
+                    path,
+                })
+                // only derives have helper attributes, and the entry point of a bare function-like
+                // or attribute implementation can't be named after it, as it's defined next to it
+                // (that of a derive is renamed, by the frontend)
+                .filter(move |entry| {
+                    (entry.naming != "helpers" || entry.kind == "derive")
+                        && (entry.naming != "implicit"
+                            || entry.source != "bare"
+                            || (entry.kind == "derive" && frontend))
+                })
+        })
+    })
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
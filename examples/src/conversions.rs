@@ -0,0 +1,29 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::LitStr;
+
+pub fn to_snake_case(input: TokenStream) -> syn::Result<TokenStream> {
+    let literal: LitStr = syn::parse2(input)?;
+    let converted = split_words(&literal.value()).join("_");
+
+    Ok(quote! { #converted })
+}
+
+pub fn to_kebab_case(input: TokenStream) -> syn::Result<TokenStream> {
+    let literal: LitStr = syn::parse2(input)?;
+    let converted = split_words(&literal.value()).join("-");
+
+    Ok(quote! { #converted })
+}
+
+/// Splits a `PascalCase` or `camelCase` string into lower case words.
+pub fn split_words(text: &str) -> Vec<String> {
+    let mut words = Vec::<String>::new();
+    for character in text.chars() {
+        match words.last_mut() {
+            Some(word) if !character.is_uppercase() => word.push(character),
+            _ => words.push(character.to_lowercase().collect()),
+        }
+    }
+    words
+}
@@ -1,26 +1,25 @@
-use proc_macro::TokenStream;
+use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, Data, DeriveInput, Expr, Fields, Lit, Meta};
+use syn::{Data, DeriveInput, Error, Expr, Fields, Lit, Meta};
 
-pub fn generate_validation(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
+pub fn generate_validation(input: DeriveInput) -> syn::Result<TokenStream> {
     let name = &input.ident;
 
     let fields = match &input.data {
         Data::Struct(data) => match &data.fields {
             Fields::Named(fields) => fields,
             _ => {
-                return quote! {
-                    compile_error!("Validate only works on structs with named fields");
-                }
-                .into()
+                return Err(Error::new_spanned(
+                    &input,
+                    "Validate only works on structs with named fields",
+                ))
             },
         },
         _ => {
-            return quote! {
-                compile_error!("Validate can only be derived for structs");
-            }
-            .into()
+            return Err(Error::new_spanned(
+                &input,
+                "Validate can only be derived for structs",
+            ))
         },
     };
 
@@ -161,5 +160,5 @@ pub fn generate_validation(input: TokenStream) -> TokenStream {
         }
     };
 
-    output.into()
+    Ok(output)
 }
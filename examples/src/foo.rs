@@ -6,7 +6,7 @@ pub fn bar(input: TokenStream) -> TokenStream {
         "baz"
     }
     "#;
-    output.parse().unwrap_or_else(|_| input)
+    output.parse().unwrap_or(input)
 }
 
 pub fn baz(input: TokenStream) -> TokenStream {
@@ -15,5 +15,5 @@ pub fn baz(input: TokenStream) -> TokenStream {
         "ofo"
     }
     "#;
-    output.parse().unwrap_or_else(|_| input)
+    output.parse().unwrap_or(input)
 }
@@ -3,3 +3,9 @@ pub fn hello(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let output = format!("\"Hello, {}\"", input_str.trim_matches('"'));
     output.parse().unwrap()
 }
+
+pub fn goodbye(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input_str = input.to_string();
+    let output = format!("\"Goodbye, {}\"", input_str.trim_matches('"'));
+    output.parse().unwrap()
+}
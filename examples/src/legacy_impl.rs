@@ -0,0 +1,29 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+
+pub fn getters(input: TokenStream) -> TokenStream {
+    let names = input.into_iter().filter_map(|token| match token {
+        TokenTree::Ident(ident) => Some(ident),
+        _ => None,
+    });
+
+    quote! {
+        #(pub fn #names(&self) -> &'static str { stringify!(#names) })*
+    }
+}
+
+pub fn counter(input: TokenStream) -> TokenStream {
+    quote! {
+        thread_local! {
+            pub static #input: ::core::cell::Cell<u32> = const { ::core::cell::Cell::new(0) };
+        }
+    }
+}
+
+pub fn bindings(input: TokenStream) -> TokenStream {
+    quote! {
+        extern "C" {
+            #input
+        }
+    }
+}
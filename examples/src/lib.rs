@@ -1,19 +1,85 @@
+//! Example procedural macros delegated with `include_proc_macro`.
+#![warn(missing_docs)]
+
 mod derive_impl_with_attrs;
 mod existing_mod;
 
 use derive_impl_with_attrs::impl_with_attributes;
 use existing_mod::fizzbuzz;
 use include_proc_macro::macros;
+use matrix::bare::{bare_attribute, bare_derive, bare_function};
 
 macros!(
+    /// Expands to a `bar` function returning `"baz"`.
     function -> foo::bar,
+    /// Prepends the attribute arguments as a doc comment to the item.
     attribute(generate_documentation) -> mod attr_impl::gen_doc,
-    derive(DefaultImpl) -> mod derive_impl::impl_default,
+    /// Renames the annotated function to the given identifier.
+    attribute(rename: syn::Ident, syn::ItemFn, alias(rename_fn)) -> rename_impl::rename,
+    /// Implements `Default` for a struct with a single `field`.
+    derive(DefaultImpl) -> catch mod derive_impl::impl_default,
+    /// Implements `NodeType`, reading the category from `#[node_category(...)]`.
     derive(NodeTypeChecks, attributes(node_category)) -> use impl_with_attributes,
-    derive(Validate, attributes(required, length, range)) -> derive_multiple_attrs::generate_validation,
+    /// Implements `Validate` from the `#[required]`, `#[length]` and `#[range]` field attributes.
+    derive(Validate: syn::DeriveInput, attributes(required, length, range)) -> try derive_multiple_attrs::generate_validation,
+    /// Expands to `"Fizz"`, `"Buzz"`, `"FizzBuzz"` or the number itself.
     function(fizz) -> use fizzbuzz,
-    function(greet) -> "hello/mod.rs"::hello,
+    /// Expands to a greeting for the given name.
+    function(greet, alias(hello, hi)) -> "hello/mod.rs" as hello_impl::hello,
+    /// Expands to a greeting for the given name.
+    function(salute, deprecated("use `greet!` instead")) -> "hello/mod.rs" as hello_impl::hello,
+    /// Expands to a method per identifier, returning the identifier.
+    function(legacy_getters, deprecated("write the methods out instead")) -> pm2 legacy_impl::getters,
+    /// Declares a thread local `Cell<u32>` counter with the given name.
+    function(legacy_counter, deprecated("use `thread_local!` instead")) -> pm2 legacy_impl::counter,
+    /// Declares the given functions in an `extern "C"` block.
+    function(legacy_bindings, deprecated("write the `extern` block out instead")) -> pm2 legacy_impl::bindings,
+    /// Prepends the attribute arguments as a doc comment to the item.
+    attribute(document, deprecated("use `#[generate_documentation]` instead")) -> use attr_impl::gen_doc,
+    /// Expands to a farewell for the given name.
+    function(farewell) -> "hello/mod.rs" as hello_impl::goodbye,
+    /// Expands to the string literal repeated the given number of times.
+    function(repeat: repeat_impl::RepeatArgs) -> try repeat_impl::repeat,
+    /// Adds `#[derive(Debug)]` to the item.
     attribute(derive_debug) -> @"test/inner.rs"::attr_derive_debug,
-    derive(DisplayImpl) -> @"test/subdir/subdir.rs"::generate_display_impl,
-    function(ofo) -> use foo::baz
+    // a file module with submodules of its own, which can't be included without the frontend
+    /// Implements `Display` as `"This is a {name}"`.
+    #[cfg(feature = "frontend")]
+    derive(DisplayImpl, alias(Describe)) -> @"test/subdir/subdir.rs" as display_impl::generate_display_impl,
+    /// Implements `Display` as `"This is a {name}"`.
+    #[cfg(feature = "frontend")]
+    derive(LegacyDisplay, deprecated("derive `DisplayImpl` instead")) -> use display_impl::generate_display_impl,
+    /// Expands to the version of this crate, from an implementation generated by the build script.
+    function -> @out/"version.rs"::version,
+    /// Folds the comma separated integer literals, one macro per file (`sum!`, `product!`).
+    #[cfg(feature = "frontend")]
+    function -> try pm2 @glob/"src/macros/fn/*.rs"::expand,
+    /// Converts the `PascalCase` string literal, one macro per `to_*` function.
+    #[cfg(feature = "frontend")]
+    function -> try pm2 conversions::to_*,
+    /// Expands to the number of top-level token trees in the input.
+    function -> @workspace/"examples/test/anchors/tokens.rs"::count_tokens,
+    /// Expands to the number of top-level identifiers in the input.
+    function -> @"CARGO_MANIFEST_DIR"/"test/anchors/idents.rs"::count_idents,
+    /// Expands to a `bar` function returning `"ofo"`.
+    function(ofo) -> foo::baz,
+    /// Expands to the string literal in upper case.
+    #[cfg(feature = "pm2")]
+    function -> pm2 pm2_impl::shout,
+    /// Expands to the integer literal doubled.
+    function -> try pm2 try_impl::double,
+    /// Expands to the single literal it's given, reporting anything else as an error.
+    function(single_literal) -> catch literal_impl::single,
+    mod strings {
+        /// Expands to the number of characters in the string literal.
+        function(length) -> try pm2 length,
+        case {
+            /// Expands to the string literal in lower case.
+            function -> try pm2 lower,
+        },
+    },
 );
+
+// one macro per kind, naming and implementation source, generated by the build script
+include!(concat!(env!("OUT_DIR"), "/matrix.rs"));
+matrix!();
@@ -0,0 +1,9 @@
+use proc_macro::{TokenStream, TokenTree};
+
+pub fn single(input: TokenStream) -> TokenStream {
+    let mut tokens = input.into_iter();
+    match (tokens.next(), tokens.next()) {
+        (Some(literal @ TokenTree::Literal(_)), None) => literal.into(),
+        _ => panic!("expected a single literal"),
+    }
+}
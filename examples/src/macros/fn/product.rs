@@ -0,0 +1,15 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{LitInt, Token};
+
+pub fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let factors = Punctuated::<LitInt, Token![,]>::parse_terminated.parse2(input)?;
+    let mut product = 1i64;
+    for factor in &factors {
+        product *= factor.base10_parse::<i64>()?;
+    }
+
+    Ok(quote! { #product })
+}
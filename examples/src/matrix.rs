@@ -0,0 +1,50 @@
+//! Implementations of the matrix of `macros!` entries (see `build/matrix.rs`), which all expand
+//! to where they're implemented.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, DeriveInput, ItemFn};
+
+pub mod bare;
+pub mod declared;
+pub mod imported;
+pub mod nested;
+
+pub fn path_function(_input: TokenStream) -> TokenStream {
+    function("path")
+}
+
+pub fn path_attribute(_args: TokenStream, item: TokenStream) -> TokenStream {
+    attribute("path", item)
+}
+
+pub fn path_derive(input: TokenStream) -> TokenStream {
+    derive("path", input)
+}
+
+/// Expands to `"<source> function"`.
+pub fn function(source: &str) -> TokenStream {
+    let source = format!("{source} function");
+    quote!(#source).into()
+}
+
+/// Replaces the body of the function with `"<source> attribute"`.
+pub fn attribute(source: &str, item: TokenStream) -> TokenStream {
+    let mut function = parse_macro_input!(item as ItemFn);
+    let source = format!("{source} attribute");
+    function.block = parse_quote!({ #source });
+    quote!(#function).into()
+}
+
+/// Implements a `SOURCE` constant of `"<source> derive"` for the type.
+pub fn derive(source: &str, input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let source = format!("{source} derive");
+    quote! {
+        impl #name {
+            pub const SOURCE: &'static str = #source;
+        }
+    }
+    .into()
+}
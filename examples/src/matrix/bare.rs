@@ -0,0 +1,13 @@
+use proc_macro::TokenStream;
+
+pub fn bare_function(_input: TokenStream) -> TokenStream {
+    super::function("bare")
+}
+
+pub fn bare_attribute(_args: TokenStream, item: TokenStream) -> TokenStream {
+    super::attribute("bare", item)
+}
+
+pub fn bare_derive(input: TokenStream) -> TokenStream {
+    super::derive("bare", input)
+}
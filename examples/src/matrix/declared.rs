@@ -0,0 +1,13 @@
+use proc_macro::TokenStream;
+
+pub fn declared_function(_input: TokenStream) -> TokenStream {
+    super::function("declared")
+}
+
+pub fn declared_attribute(_args: TokenStream, item: TokenStream) -> TokenStream {
+    super::attribute("declared", item)
+}
+
+pub fn declared_derive(input: TokenStream) -> TokenStream {
+    super::derive("declared", input)
+}
@@ -0,0 +1,13 @@
+use proc_macro::TokenStream;
+
+pub fn imported_function(_input: TokenStream) -> TokenStream {
+    super::function("imported")
+}
+
+pub fn imported_attribute(_args: TokenStream, item: TokenStream) -> TokenStream {
+    super::attribute("imported", item)
+}
+
+pub fn imported_derive(input: TokenStream) -> TokenStream {
+    super::derive("imported", input)
+}
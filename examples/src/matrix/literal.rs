@@ -0,0 +1,16 @@
+// every entry includes the whole file, but only uses one of the functions
+#![allow(dead_code)]
+
+use proc_macro::TokenStream;
+
+pub fn literal_function(_input: TokenStream) -> TokenStream {
+    crate::matrix::function("literal")
+}
+
+pub fn literal_attribute(_args: TokenStream, item: TokenStream) -> TokenStream {
+    crate::matrix::attribute("literal", item)
+}
+
+pub fn literal_derive(input: TokenStream) -> TokenStream {
+    crate::matrix::derive("literal", input)
+}
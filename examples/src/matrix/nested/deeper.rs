@@ -0,0 +1,13 @@
+use proc_macro::TokenStream;
+
+pub fn nested_function(_input: TokenStream) -> TokenStream {
+    super::super::function("nested")
+}
+
+pub fn nested_attribute(_args: TokenStream, item: TokenStream) -> TokenStream {
+    super::super::attribute("nested", item)
+}
+
+pub fn nested_derive(input: TokenStream) -> TokenStream {
+    super::super::derive("nested", input)
+}
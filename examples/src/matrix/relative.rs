@@ -0,0 +1,18 @@
+// every entry includes the whole file, but only uses one of the functions (an inner attribute
+// would end up in the middle of the including module without the frontend)
+use proc_macro::TokenStream;
+
+#[allow(dead_code)]
+pub fn relative_function(_input: TokenStream) -> TokenStream {
+    crate::matrix::function("relative")
+}
+
+#[allow(dead_code)]
+pub fn relative_attribute(_args: TokenStream, item: TokenStream) -> TokenStream {
+    crate::matrix::attribute("relative", item)
+}
+
+#[allow(dead_code)]
+pub fn relative_derive(input: TokenStream) -> TokenStream {
+    crate::matrix::derive("relative", input)
+}
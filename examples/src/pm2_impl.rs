@@ -0,0 +1,13 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::LitStr;
+
+pub fn shout(input: TokenStream) -> TokenStream {
+    let literal: LitStr = match syn::parse2(input) {
+        Ok(literal) => literal,
+        Err(err) => return err.to_compile_error(),
+    };
+    let shouted = literal.value().to_uppercase();
+
+    quote! { #shouted }
+}
@@ -0,0 +1,9 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Ident, ItemFn};
+
+pub fn rename(name: Ident, mut item: ItemFn) -> TokenStream {
+    item.sig.ident = name;
+
+    quote! { #item }
+}
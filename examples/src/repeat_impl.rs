@@ -0,0 +1,29 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{LitInt, LitStr, Token};
+
+pub struct RepeatArgs {
+    text: LitStr,
+    times: LitInt,
+}
+
+impl Parse for RepeatArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let text = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let times = input.parse()?;
+
+        Ok(RepeatArgs {
+            text,
+            times,
+        })
+    }
+}
+
+pub fn repeat(args: RepeatArgs) -> syn::Result<TokenStream> {
+    let times = args.times.base10_parse::<usize>()?;
+    let repeated = args.text.value().repeat(times);
+
+    Ok(quote! { #repeated })
+}
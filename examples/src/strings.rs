@@ -0,0 +1,12 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::LitStr;
+
+pub mod case;
+
+pub fn length(input: TokenStream) -> syn::Result<TokenStream> {
+    let literal: LitStr = syn::parse2(input)?;
+    let length = literal.value().chars().count();
+
+    Ok(quote! { #length })
+}
@@ -0,0 +1,10 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::LitStr;
+
+pub fn lower(input: TokenStream) -> syn::Result<TokenStream> {
+    let literal: LitStr = syn::parse2(input)?;
+    let lowered = literal.value().to_lowercase();
+
+    Ok(quote! { #lowered })
+}
@@ -0,0 +1,17 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Expr, ExprLit, Lit};
+
+pub fn double(input: TokenStream) -> syn::Result<TokenStream> {
+    let expr: Expr = syn::parse2(input)?;
+    let value = match &expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int),
+            ..
+        }) => int.base10_parse::<i64>()?,
+        _ => return Err(syn::Error::new_spanned(expr, "expected an integer literal")),
+    };
+    let doubled = value * 2;
+
+    Ok(quote! { #doubled })
+}
@@ -0,0 +1,7 @@
+pub fn count_idents(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let count = input
+        .into_iter()
+        .filter(|token| matches!(token, proc_macro::TokenTree::Ident(_)))
+        .count();
+    format!("{count}usize").parse().unwrap()
+}
@@ -0,0 +1,4 @@
+pub fn count_tokens(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let count = input.into_iter().count();
+    format!("{count}usize").parse().unwrap()
+}
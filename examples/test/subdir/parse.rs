@@ -0,0 +1,16 @@
+pub fn extract_type_name(input: &str) -> String {
+    // Simple parser to extract struct or enum name (not robust for production)
+    if let Some(struct_idx) = input.find("struct") {
+        let from_struct = &input[struct_idx + 6..];
+        let name_end = from_struct
+            .find('{')
+            .unwrap_or_else(|| from_struct.find(';').unwrap_or(from_struct.len()));
+        from_struct[..name_end].trim().to_string()
+    } else if let Some(enum_idx) = input.find("enum") {
+        let from_enum = &input[enum_idx + 4..];
+        let name_end = from_enum.find('{').unwrap_or(from_enum.len());
+        from_enum[..name_end].trim().to_string()
+    } else {
+        super::UNKNOWN.to_string()
+    }
+}
@@ -0,0 +1,139 @@
+use std::fs;
+use std::path::PathBuf;
+
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{bracketed, Error, Item, Path, Result, Visibility};
+
+use crate::manifest::Crate;
+
+/// A `module::*` `macros!` entry, as passed along by `macros!`.
+struct Entry {
+    krate: TokenStream,
+    meta: TokenStream,
+    kind: TokenStream,
+    markers: TokenStream,
+    module: Path,
+    filter: Option<Ident>,
+}
+
+impl Parse for Entry {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let group = |input: ParseStream| -> Result<TokenStream> {
+            let content;
+            bracketed!(content in input);
+            content.parse()
+        };
+
+        Ok(Self {
+            krate: group(input)?,
+            meta: group(input)?,
+            kind: group(input)?,
+            markers: group(input)?,
+            module: syn::parse2(group(input)?)?,
+            filter: syn::parse2(group(input)?)?,
+        })
+    }
+}
+
+pub fn expand(input: TokenStream) -> Result<TokenStream> {
+    let Entry {
+        krate,
+        meta,
+        kind,
+        markers,
+        module,
+        filter,
+    } = syn::parse2(input)?;
+    let span = module.span();
+    let module_name = module.to_token_stream().to_string().replace(' ', "");
+
+    let kind = match kind.into_iter().collect::<Vec<_>>().as_slice() {
+        [TokenTree::Ident(kind)] => kind.clone(),
+        _ => {
+            return Err(Error::new(
+                span,
+                "entries registering all the functions of a module are named after the \
+                 functions, so they can't be given a name (or helper attributes)",
+            ))
+        },
+    };
+
+    let current = Crate::current().ok_or_else(|| {
+        Error::new(
+            span,
+            "couldn't find the crate root to look for the module in (the `[lib] path` of the \
+             manifest, or `src/lib.rs`)",
+        )
+    })?;
+    let file = source_file(&current, &module).map_err(|dir| {
+        let (file, dir) = (
+            current.relative(&dir.with_extension("rs")),
+            current.relative(&dir.join("mod.rs")),
+        );
+        Error::new(
+            span,
+            format!(
+                "couldn't find the source of `{module_name}` (looked for `{file}` and `{dir}`)"
+            ),
+        )
+    })?;
+    let source = fs::read_to_string(&file)
+        .map_err(|err| Error::new(span, format!("couldn't read `{}`: {err}", file.display())))?;
+    let parsed = syn::parse_file(&source)
+        .map_err(|err| Error::new(span, format!("couldn't parse `{}`: {err}", file.display())))?;
+
+    let prefix = filter.as_ref().map(Ident::to_string).unwrap_or_default();
+    let entries = parsed
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Fn(function) if matches!(function.vis, Visibility::Public(_)) => Some(function),
+            _ => None,
+        })
+        .filter(|function| function.sig.ident.to_string().starts_with(&prefix))
+        .map(|function| {
+            let mut name = function.sig.ident.clone();
+            name.set_span(span);
+            // the entry is only compiled along with the function
+            let cfgs = function
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("cfg"));
+            quote! {
+                #krate::macros!(
+                    @emit [#meta #(#cfgs)*] [#kind(#name)] [#markers use #module::#name] [#name]
+                );
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if entries.is_empty() {
+        let functions = match &filter {
+            Some(filter) => format!("public functions starting with `{filter}`"),
+            None => "public functions".to_owned(),
+        };
+        return Err(Error::new(
+            span,
+            format!("`{module_name}` has no {functions}"),
+        ));
+    }
+
+    Ok(quote! { #(#entries)* })
+}
+
+/// Finds the file of a module declared (without `#[path]`) in the crate root, or gives the
+/// directory it'd be in as a `mod.rs` if there's none.
+fn source_file(krate: &Crate, module: &Path) -> std::result::Result<PathBuf, PathBuf> {
+    let mut dir = krate.modules().to_owned();
+    for segment in &module.segments {
+        dir.push(segment.ident.to_string());
+    }
+
+    [dir.with_extension("rs"), dir.join("mod.rs")]
+        .into_iter()
+        .find(|file| file.is_file())
+        .ok_or(dir)
+}
@@ -0,0 +1,852 @@
+//! Checks that the modules, files and functions `macros!` entries refer to exist, suggesting
+//! similarly named ones if they don't, and that no two macros (or a function-like or attribute
+//! macro and a function of the crate root) end up with the same name.
+//!
+//! Only reports what it's sure about: anything it can't resolve on its own (modules with a
+//! `#[path]`, glob imports, a crate root it can't find, ...) is left for rustc to check.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::ext::IdentExt;
+use syn::punctuated::Punctuated;
+use syn::{
+    Attribute, Error, FnArg, GenericArgument, Ident, Item, LitStr, Meta, PathArguments, Result,
+    ReturnType, Token, Type, UseTree, Visibility,
+};
+
+use crate::dsl::{self, Anchor, Keyword, Source};
+use crate::manifest::Crate;
+use crate::{glob, suggest};
+
+pub struct Checker {
+    manifest: Option<PathBuf>,
+    /// The crate, if its root file can be found (its modules aren't checked otherwise).
+    krate: Option<Crate>,
+    errors: Vec<Error>,
+}
+
+impl Checker {
+    pub fn new() -> Self {
+        Self {
+            manifest: env::var("CARGO_MANIFEST_DIR").ok().map(PathBuf::from),
+            krate: Crate::current(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// The directory the modules of the crate root live in, if it can be found.
+    fn modules(&self) -> Option<PathBuf> {
+        self.krate.as_ref().map(|krate| krate.modules().to_owned())
+    }
+
+    /// Checks all the entries, combining the errors of all of them.
+    pub fn check(mut self, items: &dsl::Items) -> Result<()> {
+        if self.manifest.is_some() {
+            self.items(items, &[]);
+        }
+        let mut registrations = Vec::new();
+        self.registrations(items, &[], &[], &mut registrations);
+        self.duplicates(&registrations);
+
+        let mut errors = self.errors.into_iter();
+        match errors.next() {
+            Some(mut error) => {
+                errors.for_each(|other| error.combine(other));
+                Err(error)
+            },
+            None => Ok(()),
+        }
+    }
+
+    fn items(&mut self, items: &dsl::Items, group: &[Ident]) {
+        for item in &items.0 {
+            match item {
+                dsl::Item::Group(inner) => {
+                    if group.is_empty() && inner.declares && self.module(&inner.path[0]).is_none() {
+                        continue;
+                    }
+                    let path = [group, &inner.path].concat();
+                    self.items(&inner.items, &path);
+                },
+                dsl::Item::Entry(entry) => self.entry(entry, group),
+            }
+        }
+    }
+
+    fn entry(&mut self, entry: &dsl::Entry, group: &[Ident]) {
+        match &entry.source {
+            Source::Path {
+                keyword,
+                segments,
+                glob,
+            } => {
+                // a function of the crate root, or a module that's only imported
+                if (group.is_empty() && glob.is_none() && segments.len() == 1)
+                    || *keyword == Some(Keyword::Use)
+                {
+                    return;
+                }
+                if group.is_empty() && self.module(&segments[0]).is_none() {
+                    return;
+                }
+                // the bulk expansion reports missing functions itself
+                if glob.is_none() {
+                    let path = [group, segments].concat();
+                    let (function, module) = path.split_last().unwrap();
+                    self.function(module, function, entry);
+                }
+            },
+            Source::File {
+                anchor,
+                path,
+                function,
+                ..
+            } => {
+                let base = match anchor {
+                    Anchor::Source => self.modules(),
+                    Anchor::Manifest => self.manifest.clone(),
+                    // the glob expansion reports missing files itself
+                    Anchor::Glob | Anchor::Environment(_) => None,
+                };
+                let Some(base) = base else {
+                    return;
+                };
+                if let Some(file) = self.file(&base, path) {
+                    if let Some(items) = parse(&file) {
+                        let location = format!("`{}`", path.value());
+                        self.function_in(&items, function, &location, entry);
+                    }
+                }
+            },
+        }
+    }
+
+    /// Collects the macros registered by the entries, along with the cfg predicates they're
+    /// compiled with.
+    fn registrations(
+        &self,
+        items: &dsl::Items,
+        group: &[Ident],
+        cfgs: &[String],
+        into: &mut Vec<Registration>,
+    ) {
+        for item in &items.0 {
+            match item {
+                dsl::Item::Group(inner) => {
+                    let path = [group, &inner.path].concat();
+                    let cfgs = [cfgs, &self::cfgs(&inner.attrs)].concat();
+                    self.registrations(&inner.items, &path, &cfgs, into);
+                },
+                dsl::Item::Entry(entry) => {
+                    for (name, attrs) in self.names(entry, group) {
+                        let mut cfgs =
+                            [cfgs, &self::cfgs(&entry.attrs), &self::cfgs(&attrs)].concat();
+                        cfgs.sort();
+                        cfgs.dedup();
+                        into.push(Registration {
+                            name: name.unraw().to_string(),
+                            span: name.span(),
+                            cfgs,
+                            derive: entry.kind == "derive",
+                        });
+                    }
+                },
+            }
+        }
+    }
+
+    /// The names (and aliases) of the macros an entry registers, along with the attributes of
+    /// the functions they're registered for, if any. The names of glob entries are only known if
+    /// their module or files can be found.
+    fn names(&self, entry: &dsl::Entry, group: &[Ident]) -> Vec<(Ident, Vec<Attribute>)> {
+        if let Some(name) = &entry.name {
+            return [name]
+                .into_iter()
+                .chain(&entry.aliases)
+                .map(|name| (name.clone(), Vec::new()))
+                .collect();
+        }
+
+        match &entry.source {
+            Source::Path {
+                segments,
+                glob: None,
+                ..
+            } => vec![(segments.last().unwrap().clone(), Vec::new())],
+            Source::Path {
+                segments,
+                glob: Some(prefix),
+                ..
+            } => {
+                let module = [group, segments].concat();
+                let items = self
+                    .modules()
+                    .and_then(|src| resolve(&src, &module))
+                    .unwrap_or_default();
+                let span = prefix.as_ref().unwrap_or(segments.last().unwrap()).span();
+                let prefix = prefix.as_ref().map(Ident::to_string).unwrap_or_default();
+                items
+                    .into_iter()
+                    .filter_map(|item| match item {
+                        Item::Fn(function) if matches!(function.vis, Visibility::Public(_)) => {
+                            Some(function)
+                        },
+                        _ => None,
+                    })
+                    .filter(|function| function.sig.ident.to_string().starts_with(&prefix))
+                    .map(|function| {
+                        let mut name = function.sig.ident;
+                        name.set_span(span);
+                        (name, function.attrs)
+                    })
+                    .collect()
+            },
+            Source::File {
+                anchor: Anchor::Glob,
+                path,
+                ..
+            } => {
+                let Some(manifest) = &self.manifest else {
+                    return Vec::new();
+                };
+                glob::matching_files(manifest, &path.value())
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|file| {
+                        let stem = Path::new(file).file_stem()?.to_str()?;
+                        let mut name = syn::parse_str::<Ident>(stem).ok()?;
+                        name.set_span(path.span());
+                        Some((name, Vec::new()))
+                    })
+                    .collect()
+            },
+            Source::File {
+                function,
+                ..
+            } => vec![(function.clone(), Vec::new())],
+        }
+    }
+
+    /// Reports the macros sharing their name with an earlier one that is compiled whenever
+    /// they are or the other way around (its cfg predicates are a subset of theirs, or a
+    /// superset; entries with none clash with every other), or named after their entry point
+    /// like a function the crate root defines or imports, pointing at both. Entries whose cfgs
+    /// only partly overlap may well exclude each other, and are left to the compiler.
+    fn duplicates(&mut self, registrations: &[Registration]) {
+        let root = self.root_functions();
+        for (index, registration) in registrations.iter().enumerate() {
+            let name = &registration.name;
+            if let Some(earlier) = registrations[..index]
+                .iter()
+                .find(|earlier| earlier.name == *name && nested(&earlier.cfgs, &registration.cfgs))
+            {
+                self.errors.push(Error::new(
+                    registration.span,
+                    format!("the macro `{name}` is already registered by an earlier entry"),
+                ));
+                self.errors.push(Error::new(
+                    earlier.span,
+                    format!("`{name}` is first registered here"),
+                ));
+            } else if let Some((_, function)) = root
+                .iter()
+                .find(|(function, _)| !registration.derive && function == name)
+            {
+                self.errors.push(Error::new(
+                    registration.span,
+                    format!(
+                        "the macro `{name}` clashes with {function} at the crate root, give the \
+                         macro another name"
+                    ),
+                ));
+            }
+        }
+    }
+
+    /// The functions (and other values) the crate root file defines or imports unconditionally,
+    /// along with a description of where they come from. Imports are only included if they can
+    /// be resolved to a value in the crate.
+    fn root_functions(&self) -> Vec<(String, String)> {
+        let Some(krate) = &self.krate else {
+            return Vec::new();
+        };
+        let (src, Some(items)) = (krate.modules(), parse(&krate.root)) else {
+            return Vec::new();
+        };
+
+        let mut functions = Vec::new();
+        for item in items.iter().filter(|item| !is_cfgd(item)) {
+            match item {
+                Item::Fn(function) => {
+                    let name = function.sig.ident.unraw().to_string();
+                    functions.push((name.clone(), format!("the function `{name}` defined")));
+                },
+                Item::Use(used) => {
+                    let mut imports = Vec::new();
+                    imported(&used.tree, &mut Vec::new(), &mut imports);
+                    for (path, name) in imports {
+                        if is_value(src, &path) {
+                            let path = path.iter().map(Ident::to_string).collect::<Vec<_>>();
+                            functions.push((name, format!("`use {}` imported", path.join("::"))));
+                        }
+                    }
+                },
+                _ => {},
+            }
+        }
+        functions
+    }
+
+    /// Finds the file of a module declared in the crate root, reporting it if there's none.
+    fn module(&mut self, name: &Ident) -> Option<PathBuf> {
+        let krate = self.krate.as_ref()?;
+        let src = krate.modules();
+        if let Some((file, _)) = module_file(src, &name.to_string()) {
+            return Some(file);
+        }
+
+        let root = krate.root.file_stem().and_then(|stem| stem.to_str());
+        let modules = entries(src)
+            .into_iter()
+            .filter_map(|entry| {
+                let module = entry.strip_suffix(".rs").unwrap_or(&entry);
+                (Some(module) != root && module != "main").then(|| module.to_owned())
+            })
+            .collect::<Vec<_>>();
+        let (file, dir) = (
+            krate.relative(&src.join(format!("{name}.rs"))),
+            krate.relative(&src.join(name.to_string()).join("mod.rs")),
+        );
+        self.errors.push(Error::new(
+            name.span(),
+            format!(
+                "couldn't find the module `{name}` (looked for `{file}` and `{dir}`){}",
+                suggest::did_you_mean(&name.to_string(), &modules)
+            ),
+        ));
+        None
+    }
+
+    /// Checks that a function exists in a module, if the module can be resolved.
+    fn function(&mut self, module: &[Ident], function: &Ident, entry: &dsl::Entry) {
+        let Some(src) = self.modules() else {
+            return;
+        };
+        let Some(items) = resolve(&src, module) else {
+            return;
+        };
+        let module = module
+            .iter()
+            .map(Ident::to_string)
+            .collect::<Vec<_>>()
+            .join("::");
+        self.function_in(&items, function, &format!("module `{module}`"), entry);
+    }
+
+    fn function_in(
+        &mut self,
+        items: &[Item],
+        function: &Ident,
+        location: &str,
+        entry: &dsl::Entry,
+    ) {
+        let name = function.to_string();
+        let defined = items.iter().find_map(|item| match item {
+            Item::Fn(defined) if defined.sig.ident == name => Some(defined),
+            _ => None,
+        });
+        if let Some(defined) = defined {
+            self.signature(&defined.sig, function, entry, items);
+            return;
+        }
+        if items.iter().any(|item| provides(item, &name)) {
+            return;
+        }
+
+        let functions = items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Fn(function) => Some(function.sig.ident.to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        self.errors.push(Error::new(
+            function.span(),
+            format!(
+                "cannot find function `{name}` in {location}{}",
+                suggest::did_you_mean(&name, &functions)
+            ),
+        ));
+    }
+
+    /// Checks that an implementation takes as many arguments as the entry point passes it, and
+    /// of the types it passes (returning the type it expects), as far as the file they're
+    /// written in tells for sure. Anything else is left to the checks of the entry point.
+    fn signature(
+        &mut self,
+        signature: &syn::Signature,
+        function: &Ident,
+        entry: &dsl::Entry,
+        items: &[Item],
+    ) {
+        let arguments = if entry.kind == "attribute" { 2 } else { 1 };
+        let found = signature.inputs.len();
+        let (input, output) = expected_shapes(entry);
+        let inputs = signature.inputs.iter().map(|input| match input {
+            FnArg::Typed(typed) => shape(&typed.ty, items),
+            FnArg::Receiver(_) => Shape::Other,
+        });
+        let returned = match &signature.output {
+            ReturnType::Type(_, ty) => shape(ty, items),
+            ReturnType::Default => Shape::Other,
+        };
+        let mismatch = if found != arguments {
+            format!(
+                "takes {found} argument{}",
+                if found == 1 { "" } else { "s" }
+            )
+        } else if inputs.into_iter().any(|found| !found.fits(&input)) || !returned.fits(&output) {
+            format!("is `{}`", found_signature(signature))
+        } else {
+            return;
+        };
+
+        let kind = if entry.kind == "function" {
+            "function-like".to_owned()
+        } else {
+            entry.kind.to_string()
+        };
+        self.errors.push(Error::new(
+            function.span(),
+            format!(
+                "{kind} implementations must be `{}`, but `{function}` {mismatch}",
+                expected_signature(entry, arguments),
+            ),
+        ));
+    }
+
+    /// Checks that an implementation file exists, suggesting its closest sibling otherwise.
+    fn file(&mut self, base: &Path, path: &LitStr) -> Option<PathBuf> {
+        let file = base.join(path.value());
+        if file.is_file() {
+            return Some(file);
+        }
+
+        let relative = path.value();
+        let (dir, name) = match relative.rsplit_once('/') {
+            Some((dir, name)) => (format!("{dir}/"), name.to_owned()),
+            None => (String::new(), relative.clone()),
+        };
+        let siblings = entries(&base.join(&dir))
+            .into_iter()
+            .map(|sibling| format!("{dir}{sibling}"))
+            .collect::<Vec<_>>();
+        self.errors.push(Error::new(
+            path.span(),
+            format!(
+                "couldn't find `{}`{}",
+                file.display(),
+                suggest::did_you_mean(&format!("{dir}{name}"), &siblings)
+            ),
+        ));
+        None
+    }
+}
+
+/// The signature `macros!` expects of the implementation of an entry, given its markers and
+/// input types.
+fn expected_signature(entry: &dsl::Entry, arguments: usize) -> String {
+    let marked = |marker: &str| entry.markers.iter().any(|ident| ident == marker);
+    let input = if marked("pm2") { "proc_macro2::TokenStream" } else { "TokenStream" };
+    let inputs = if entry.types.is_empty() {
+        vec![input.to_owned(); arguments]
+    } else {
+        entry.types.iter().map(render).collect()
+    };
+    let mut output = if marked("pm2") || !entry.types.is_empty() {
+        "proc_macro2::TokenStream".to_owned()
+    } else {
+        "TokenStream".to_owned()
+    };
+    if marked("try") {
+        output = format!("Result<{output}, _>");
+    }
+    format!("fn({}) -> {output}", inputs.join(", "))
+}
+
+/// The signature of an implementation, written like the one it's expected to have.
+fn found_signature(signature: &syn::Signature) -> String {
+    let inputs = signature
+        .inputs
+        .iter()
+        .map(|input| match input {
+            FnArg::Typed(typed) => render(&typed.ty),
+            FnArg::Receiver(receiver) => render(&receiver.ty),
+        })
+        .collect::<Vec<_>>();
+    match &signature.output {
+        ReturnType::Type(_, ty) => format!("fn({}) -> {}", inputs.join(", "), render(ty)),
+        ReturnType::Default => format!("fn({})", inputs.join(", ")),
+    }
+}
+
+/// A type as it's written, without the spaces of its tokens.
+fn render(ty: &Type) -> String {
+    [
+        (" :: ", "::"),
+        (":: ", "::"),
+        (" < ", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        ("& ", "&"),
+    ]
+    .iter()
+    .fold(ty.to_token_stream().to_string(), |rendered, (from, to)| {
+        rendered.replace(from, to)
+    })
+}
+
+/// The types an entry point passes its implementation, and expects back.
+fn expected_shapes(entry: &dsl::Entry) -> (Shape, Shape) {
+    let marked = |marker: &str| entry.markers.iter().any(|ident| ident == marker);
+    let (input, mut output) = if !entry.types.is_empty() {
+        (Shape::Unknown, Shape::TokenStream2)
+    } else if marked("pm2") {
+        (Shape::TokenStream2, Shape::TokenStream2)
+    } else {
+        (Shape::TokenStream, Shape::TokenStream)
+    };
+    if marked("try") {
+        output = Shape::Result(Box::new(output));
+    }
+    (input, output)
+}
+
+/// What a type in the signature of an implementation is, as far as its file tells for sure.
+#[derive(PartialEq)]
+enum Shape {
+    /// `proc_macro::TokenStream`.
+    TokenStream,
+    /// `proc_macro2::TokenStream`.
+    TokenStream2,
+    Result(Box<Shape>),
+    /// Certainly none of the above, e.g. `()`, `&str` or `String`.
+    Other,
+    /// Can't be told without resolving more than the file, e.g. a type alias or a generic.
+    Unknown,
+}
+
+impl Shape {
+    /// Whether a type of this shape may be of the expected one.
+    fn fits(&self, expected: &Shape) -> bool {
+        match (self, expected) {
+            (Shape::Unknown, _) | (_, Shape::Unknown) => true,
+            (Shape::Result(found), Shape::Result(expected)) => found.fits(expected),
+            (found, expected) => found == expected,
+        }
+    }
+}
+
+/// The types of the prelude, which only an item or import of the file can shadow.
+const PRELUDE: &[&str] = &[
+    "String", "Vec", "Option", "Box", "bool", "char", "str", "u8", "u16", "u32", "u64", "u128",
+    "usize", "i8", "i16", "i32", "i64", "i128", "isize", "f32", "f64",
+];
+
+fn shape(ty: &Type, items: &[Item]) -> Shape {
+    match ty {
+        Type::Paren(inner) => shape(&inner.elem, items),
+        Type::Group(inner) => shape(&inner.elem, items),
+        Type::Path(path) if path.qself.is_none() => path_shape(&path.path, items),
+        Type::Array(_)
+        | Type::BareFn(_)
+        | Type::Never(_)
+        | Type::Ptr(_)
+        | Type::Reference(_)
+        | Type::Slice(_)
+        | Type::TraitObject(_)
+        | Type::Tuple(_) => Shape::Other,
+        _ => Shape::Unknown,
+    }
+}
+
+fn path_shape(path: &syn::Path, items: &[Item]) -> Shape {
+    let Some(last) = path.segments.last() else {
+        return Shape::Unknown;
+    };
+    if last.ident == "Result" {
+        return match &last.arguments {
+            PathArguments::AngleBracketed(arguments) => match arguments.args.first() {
+                Some(GenericArgument::Type(ok)) => Shape::Result(Box::new(shape(ok, items))),
+                _ => Shape::Unknown,
+            },
+            _ => Shape::Unknown,
+        };
+    }
+
+    let mut segments = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>();
+    if path.segments.len() == 1 && path.leading_colon.is_none() {
+        let name = last.ident.to_string();
+        let mut imports = Vec::new();
+        for item in items {
+            if let Item::Use(used) = item {
+                imported(&used.tree, &mut Vec::new(), &mut imports);
+            }
+        }
+        if let Some((import, _)) = imports.iter().find(|(_, imported)| *imported == name) {
+            segments = import.iter().map(Ident::to_string).collect();
+        } else if items.iter().any(|item| defines_type(item, &name)) {
+            return Shape::Unknown;
+        } else {
+            return if PRELUDE.contains(&name.as_str()) { Shape::Other } else { Shape::Unknown };
+        }
+    }
+
+    match segments
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        [.., "proc_macro", "TokenStream"] => Shape::TokenStream,
+        [.., "proc_macro2", "TokenStream"] => Shape::TokenStream2,
+        ["std" | "core" | "alloc", ..] => Shape::Other,
+        _ => Shape::Unknown,
+    }
+}
+
+/// Whether an item of a file might make a type `name` available in it: defines or imports it,
+/// or can't be seen through (glob imports and macros).
+fn defines_type(item: &Item, name: &str) -> bool {
+    match item {
+        Item::Struct(defined) => defined.ident == name,
+        Item::Enum(defined) => defined.ident == name,
+        Item::Union(defined) => defined.ident == name,
+        Item::Type(defined) => defined.ident == name,
+        Item::Trait(defined) => defined.ident == name,
+        Item::Use(_) | Item::Macro(_) | Item::Verbatim(_) => provides(item, name),
+        _ => false,
+    }
+}
+
+/// A macro registered by an entry.
+struct Registration {
+    name: String,
+    span: Span,
+    /// The cfg predicates the macro is compiled with, sorted.
+    cfgs: Vec<String>,
+    /// Whether it's a derive, whose entry point is named `__derive_Name` instead.
+    derive: bool,
+}
+
+/// Whether one of two sets of cfg predicates implies the other, so that an item compiled with
+/// the one is compiled along with an item compiled with the other whenever either is.
+fn nested(a: &[String], b: &[String]) -> bool {
+    a.iter().all(|cfg| b.contains(cfg)) || b.iter().all(|cfg| a.contains(cfg))
+}
+
+/// The predicates of the `#[cfg(...)]`s among the attributes, as written, with those of
+/// `all(...)` taken one by one.
+fn cfgs(attrs: &[Attribute]) -> Vec<String> {
+    fn predicates(meta: Meta, into: &mut Vec<String>) {
+        match meta {
+            Meta::List(list) if list.path.is_ident("all") => {
+                match list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) {
+                    Ok(inner) => inner.into_iter().for_each(|meta| predicates(meta, into)),
+                    Err(_) => into.push(list.to_token_stream().to_string()),
+                }
+            },
+            meta => into.push(meta.to_token_stream().to_string()),
+        }
+    }
+
+    let mut into = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("cfg")) {
+        match attr.parse_args::<Meta>() {
+            Ok(meta) => predicates(meta, &mut into),
+            Err(_) => into.push(attr.to_token_stream().to_string()),
+        }
+    }
+    into
+}
+
+fn is_cfgd(item: &Item) -> bool {
+    let attrs = match item {
+        Item::Fn(function) => &function.attrs,
+        Item::Use(used) => &used.attrs,
+        _ => return false,
+    };
+    !cfgs(attrs).is_empty()
+}
+
+/// Collects the paths a `use` tree imports by name, along with the name they're imported as.
+fn imported(tree: &UseTree, prefix: &mut Vec<Ident>, into: &mut Vec<(Vec<Ident>, String)>) {
+    match tree {
+        UseTree::Path(path) => {
+            prefix.push(path.ident.clone());
+            imported(&path.tree, prefix, into);
+            prefix.pop();
+        },
+        UseTree::Name(used) if used.ident != "self" => into.push((
+            [prefix.as_slice(), std::slice::from_ref(&used.ident)].concat(),
+            used.ident.unraw().to_string(),
+        )),
+        UseTree::Rename(rename) if rename.ident != "self" => into.push((
+            [prefix.as_slice(), std::slice::from_ref(&rename.ident)].concat(),
+            rename.rename.unraw().to_string(),
+        )),
+        UseTree::Group(group) => {
+            for tree in &group.items {
+                imported(tree, prefix, into);
+            }
+        },
+        _ => {},
+    }
+}
+
+/// Whether a path imported at the crate root resolves to a function, constant or static of the
+/// crate (anything else, or anything that can't be resolved, might not be a value).
+fn is_value(src: &Path, path: &[Ident]) -> bool {
+    let path = match path.first() {
+        Some(first) if first == "crate" || first == "self" => &path[1..],
+        _ => path,
+    };
+    let Some((name, module)) = path.split_last() else {
+        return false;
+    };
+    resolve(src, module).is_some_and(|items| {
+        items.iter().any(|item| match item {
+            Item::Fn(function) => function.sig.ident == *name,
+            Item::Const(constant) => constant.ident == *name,
+            Item::Static(statik) => statik.ident == *name,
+            _ => false,
+        })
+    })
+}
+
+/// Finds the file of module `name` declared in a file whose modules live in `dir`, along with
+/// the directory of its own modules.
+fn module_file(dir: &Path, name: &str) -> Option<(PathBuf, PathBuf)> {
+    [dir.join(format!("{name}.rs")), dir.join(name).join("mod.rs")]
+        .into_iter()
+        .find(|file| file.is_file())
+        .map(|file| (file, dir.join(name)))
+}
+
+/// The items of a module of the crate root, if it can be resolved without `#[path]`s.
+fn resolve(src: &Path, module: &[Ident]) -> Option<Vec<Item>> {
+    let (root, rest) = module.split_first()?;
+    let (file, mut dir) = module_file(src, &root.to_string())?;
+    let mut items = parse(&file)?;
+
+    for segment in rest {
+        let declaration = items.iter().find_map(|item| match item {
+            Item::Mod(declaration) if declaration.ident == *segment => Some(declaration.clone()),
+            _ => None,
+        })?;
+        if declaration
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("path"))
+        {
+            return None;
+        }
+        items = match declaration.content {
+            Some((_, content)) => content,
+            None => parse(&module_file(&dir, &segment.to_string())?.0)?,
+        };
+        dir.push(segment.to_string());
+    }
+    Some(items)
+}
+
+fn parse(file: &Path) -> Option<Vec<Item>> {
+    let source = fs::read_to_string(file).ok()?;
+    syn::parse_file(&source).ok().map(|file| file.items)
+}
+
+/// Whether an item might make a function `name` available: defines or imports it, or can't be
+/// seen through (glob imports and macros).
+fn provides(item: &Item, name: &str) -> bool {
+    fn imports(tree: &UseTree, name: &str) -> bool {
+        match tree {
+            UseTree::Path(path) => imports(&path.tree, name),
+            UseTree::Name(used) => used.ident == name,
+            UseTree::Rename(rename) => rename.rename == name,
+            UseTree::Glob(_) => true,
+            UseTree::Group(group) => group.items.iter().any(|tree| imports(tree, name)),
+        }
+    }
+
+    match item {
+        Item::Fn(function) => function.sig.ident == name,
+        Item::Use(used) => imports(&used.tree, name),
+        Item::Macro(_) | Item::Verbatim(_) => true,
+        _ => false,
+    }
+}
+
+/// The names in a directory, skipping hidden ones.
+fn entries(dir: &Path) -> Vec<String> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+                .filter(|name| !name.starts_with('.'))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shape_in(ty: &str, file: &str) -> Shape {
+        let file = syn::parse_file(file).unwrap();
+        shape(&syn::parse_str(ty).unwrap(), &file.items)
+    }
+
+    #[test]
+    fn test_shape() {
+        let pm = "use proc_macro::TokenStream;";
+        assert!(shape_in("TokenStream", pm) == Shape::TokenStream);
+        assert!(shape_in("proc_macro2::TokenStream", pm) == Shape::TokenStream2);
+        assert!(
+            shape_in(
+                "TokenStream",
+                "use proc_macro2::TokenStream as TokenStream;"
+            ) == Shape::TokenStream2
+        );
+        assert!(
+            shape_in("Result<TokenStream, String>", pm)
+                == Shape::Result(Box::new(Shape::TokenStream))
+        );
+        assert!(shape_in("String", pm) == Shape::Other);
+        assert!(shape_in("&str", pm) == Shape::Other);
+        assert!(shape_in("()", pm) == Shape::Other);
+        assert!(shape_in("std::string::String", pm) == Shape::Other);
+
+        // aliases, shadowed prelude types, glob imports and generics can't be told
+        assert!(shape_in("TokenStream", "") == Shape::Unknown);
+        assert!(shape_in("Tokens", "type Tokens = proc_macro::TokenStream;") == Shape::Unknown);
+        assert!(shape_in("String", "struct String;") == Shape::Unknown);
+        assert!(shape_in("String", "use proc_macro::*;") == Shape::Unknown);
+        assert!(shape_in("T", pm) == Shape::Unknown);
+
+        assert!(Shape::Unknown.fits(&Shape::TokenStream));
+        assert!(!Shape::Other.fits(&Shape::TokenStream));
+        assert!(!Shape::TokenStream.fits(&Shape::Result(Box::new(Shape::TokenStream))));
+        assert!(Shape::Result(Box::new(Shape::Unknown))
+            .fits(&Shape::Result(Box::new(Shape::TokenStream2))));
+    }
+}
@@ -0,0 +1,801 @@
+//! The `macros!` grammar, parsed into entries and groups of them.
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{
+    braced, bracketed, parenthesized, token, Attribute, Error, Ident, LitStr, Result, Token, Type,
+};
+
+use crate::check::Checker;
+use crate::suggest;
+
+pub const KINDS: &[&str] = &["function", "attribute", "derive"];
+pub const MARKERS: &[&str] = &["pm2", "try", "catch"];
+pub const ANCHORS: &[&str] = &["@out/", "@workspace/", "@glob/"];
+
+/// Parses and checks a `macros!` invocation, and hands it back to `macros!` to expand.
+pub fn expand(input: TokenStream) -> Result<TokenStream> {
+    let (krate, entries) = syn::parse::Parser::parse2(
+        |input: ParseStream| {
+            let krate;
+            bracketed!(krate in input);
+            Ok((krate.parse::<TokenStream>()?, input.parse::<TokenStream>()?))
+        },
+        input,
+    )?;
+
+    let (items, tokens): (Vec<_>, Vec<_>) = split(entries)
+        .into_iter()
+        .map(|tokens| Ok((syn::parse::Parser::parse2(item, tokens.clone())?, tokens)))
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+    let items = Items(items);
+    Checker::new().check(&items)?;
+
+    // the module roots are declared here, once each, gated by the cfgs of all the entries
+    // declaring them (rather than by `macros!`, comparing each declaration with the earlier
+    // ones), so that every item is expanded on its own and the expansion doesn't get deeper
+    // with every item
+    let mut declarations = Vec::<(Declaration, Vec<Option<TokenStream>>)>::new();
+    for item in &items.0 {
+        let mut own = Vec::new();
+        item.declarations(&[], true, &mut own);
+        for declaration in own {
+            let predicate = declaration.predicate();
+            match declarations
+                .iter_mut()
+                .find(|(earlier, _)| earlier.root == declaration.root)
+            {
+                Some((_, predicates)) => predicates.push(predicate),
+                None => declarations.push((declaration, vec![predicate])),
+            }
+        }
+    }
+    let declarations = declarations.iter().map(|(declaration, predicates)| {
+        let item = declaration.to_tokens(&krate);
+        match predicates
+            .iter()
+            .map(Option::as_ref)
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(predicates) => quote! { #[cfg(any(#(#predicates),*))] #item },
+            None => item,
+        }
+    });
+
+    Ok(quote! {
+        #(#declarations)*
+        #(#krate::macros!(@scope [frontend] [] [] [] #tokens);)*
+    })
+}
+
+/// Splits the items at the top-level commas, dropping a trailing one.
+fn split(entries: TokenStream) -> Vec<TokenStream> {
+    let mut items = vec![TokenStream::new()];
+    for token in entries {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => items.push(TokenStream::new()),
+            token => items.last_mut().unwrap().extend([token]),
+        }
+    }
+    if items.len() > 1 && items.last().is_some_and(TokenStream::is_empty) {
+        items.pop();
+    }
+    items
+}
+
+/// Parses a single item, which must be followed by a comma (if anything).
+fn item(input: ParseStream) -> Result<Item> {
+    let item = input.parse()?;
+    if !input.is_empty() {
+        return Err(input.error("expected `,` between entries"));
+    }
+    Ok(item)
+}
+
+/// A module root declared at the crate root, and the attributes of the entry declaring it.
+pub struct Declaration {
+    pub root: Ident,
+    pub attrs: Vec<Attribute>,
+    /// The file of a named file module (`"file.rs" as module`), as written.
+    pub file: Option<TokenStream>,
+}
+
+impl Declaration {
+    /// The cfg predicate the declaring entry is compiled under, `None` if it always is.
+    fn predicate(&self) -> Option<TokenStream> {
+        let cfgs = self
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("cfg"))
+            .filter_map(|attr| attr.meta.require_list().ok())
+            .map(|list| &list.tokens)
+            .collect::<Vec<_>>();
+        (!cfgs.is_empty()).then(|| quote! { all(#(#cfgs),*) })
+    }
+
+    /// The module declaration, as `macros!` would have emitted it.
+    fn to_tokens(&self, krate: &TokenStream) -> TokenStream {
+        let root = &self.root;
+        match &self.file {
+            Some(file) => quote! { #krate::__delegate!(@module #root #file); },
+            None => quote! { mod #root; },
+        }
+    }
+}
+
+/// The comma separated items of a `macros!` invocation (or of a group).
+pub struct Items(pub Vec<Item>);
+
+pub enum Item {
+    Entry(Entry),
+    Group(Group),
+}
+
+/// `mod module { items }`, `module { items }` or `use module { items }`.
+pub struct Group {
+    /// The `#[cfg(...)]`s of the group.
+    pub attrs: Vec<Attribute>,
+    /// Whether the group declares its module, i.e. isn't a `use` group.
+    pub declares: bool,
+    /// The `mod` the group is written with, if any.
+    pub keyword: Option<Token![mod]>,
+    pub path: Vec<Ident>,
+    pub items: Items,
+}
+
+/// `kind(spec) -> markers source`, of which only the attributes, the kind, the name, input
+/// types and aliases, the markers and the source matter once parsed.
+pub struct Entry {
+    pub attrs: Vec<Attribute>,
+    pub kind: Ident,
+    /// The name given in the spec, if any.
+    pub name: Option<Ident>,
+    /// The types the input is parsed into, if any.
+    pub types: Vec<Type>,
+    /// The other names the macro is registered under (`alias(..)`).
+    pub aliases: Vec<Ident>,
+    pub markers: Vec<Ident>,
+    pub source: Source,
+}
+
+pub enum Source {
+    /// `module::function`, with an optional `mod` or `use`, or `module::prefix*` for all the
+    /// (matching) public functions of the module.
+    Path {
+        keyword: Option<Keyword>,
+        segments: Vec<Ident>,
+        glob: Option<Option<Ident>>,
+    },
+    /// `"file.rs"::function`, with an optional anchor (`@`, `@out/`, ...) and module name.
+    File {
+        anchor: Anchor,
+        path: LitStr,
+        module: Option<Ident>,
+        function: Ident,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Keyword {
+    Mod,
+    Use,
+}
+
+pub enum Anchor {
+    /// A plain literal, relative to the crate root file.
+    Source,
+    /// `@"..."`, relative to the manifest directory.
+    Manifest,
+    /// `@glob/"..."`, the files matching a path relative to the manifest directory.
+    Glob,
+    /// `@out/`, `@workspace/` and `@"ENV_VAR"/`, which are only known when compiling.
+    Environment(TokenStream),
+}
+
+impl Anchor {
+    /// The anchored path, as written.
+    fn to_tokens(&self, path: &LitStr) -> TokenStream {
+        match self {
+            Anchor::Source => quote! { #path },
+            Anchor::Manifest => quote! { @#path },
+            Anchor::Glob => quote! { @glob / #path },
+            Anchor::Environment(anchor) => quote! { @#anchor / #path },
+        }
+    }
+}
+
+impl Parse for Items {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut items = Vec::new();
+        while !input.is_empty() {
+            items.push(input.parse()?);
+            if !input.is_empty() && !input.peek(Token![,]) {
+                return Err(input.error("expected `,` between entries"));
+            }
+            input.parse::<Option<Token![,]>>()?;
+        }
+
+        Ok(Self(items))
+    }
+}
+
+impl Parse for Item {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        if !is_group(input) {
+            return input.parse().map(|entry| {
+                Item::Entry(Entry {
+                    attrs,
+                    ..entry
+                })
+            });
+        }
+
+        if let Some(attr) = attrs.iter().find(|attr| !attr.path().is_ident("cfg")) {
+            return Err(Error::new(
+                attr.span(),
+                "only `#[cfg(...)]` attributes can be applied to a group of entries",
+            ));
+        }
+        input.parse().map(|group| {
+            Item::Group(Group {
+                attrs,
+                ..group
+            })
+        })
+    }
+}
+
+impl Item {
+    /// Collects the module roots the item declares at the crate root, as `macros!` does.
+    fn declarations(&self, outer: &[Attribute], top_level: bool, into: &mut Vec<Declaration>) {
+        match self {
+            Item::Group(group) => {
+                let attrs = [outer, &group.attrs].concat();
+                if top_level && group.declares {
+                    into.push(Declaration {
+                        root: group.path[0].clone(),
+                        attrs: attrs.clone(),
+                        file: None,
+                    });
+                }
+                for item in &group.items.0 {
+                    item.declarations(&attrs, false, into);
+                }
+            },
+            Item::Entry(entry) => {
+                let (root, file) = match &entry.source {
+                    Source::Path {
+                        keyword,
+                        segments,
+                        glob,
+                    } if top_level
+                        && *keyword != Some(Keyword::Use)
+                        && (segments.len() > 1 || glob.is_some()) =>
+                    {
+                        (&segments[0], None)
+                    },
+                    Source::File {
+                        anchor,
+                        path,
+                        module: Some(module),
+                        ..
+                    } => (module, Some(anchor.to_tokens(path))),
+                    _ => return,
+                };
+                into.push(Declaration {
+                    root: root.clone(),
+                    attrs: [outer, &entry.attrs].concat(),
+                    file,
+                });
+            },
+        }
+    }
+}
+
+/// Whether the input continues with a group (a module path followed by braces).
+fn is_group(input: ParseStream) -> bool {
+    if input.peek(Token![mod]) || input.peek(Token![use]) {
+        return !input.peek2(Ident::peek_any) || {
+            let fork = input.fork();
+            let _ = fork.call(Ident::parse_any);
+            is_group(&fork)
+        };
+    }
+
+    let fork = input.fork();
+    while fork.call(Ident::parse_any).is_ok() {
+        if fork.peek(token::Brace) {
+            return true;
+        }
+        if fork.parse::<Token![::]>().is_err() {
+            return false;
+        }
+    }
+    false
+}
+
+impl Parse for Group {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let declares = input.parse::<Option<Token![use]>>()?.is_none();
+        let keyword = if declares { input.parse()? } else { None };
+        let path = path(input)?;
+        let content;
+        braced!(content in input);
+        let items = content.parse::<Items>()?;
+
+        for item in &items.0 {
+            if let Item::Group(Group {
+                keyword: Some(keyword),
+                ..
+            }) = item
+            {
+                return Err(Error::new(
+                    keyword.span,
+                    "`mod` groups can only be used at the top level, groups nested in another \
+                     one refer to a module of the enclosing group",
+                ));
+            }
+        }
+
+        Ok(Self {
+            attrs: Vec::new(),
+            declares,
+            keyword,
+            path,
+            items,
+        })
+    }
+}
+
+impl Parse for Entry {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let kind = input.call(Ident::parse_any).map_err(|err| {
+            Error::new(
+                err.span(),
+                "expected an entry, like `function -> implementation`",
+            )
+        })?;
+        if !KINDS.contains(&kind.to_string().as_str()) {
+            return Err(Error::new(
+                kind.span(),
+                format!(
+                    "unknown macro kind `{kind}`, expected `function`, `attribute` or `derive`{}",
+                    suggest::did_you_mean(&kind.to_string(), KINDS)
+                ),
+            ));
+        }
+        let (mut name, mut types, mut aliases) = (None, Vec::new(), Vec::new());
+        if input.peek(token::Paren) {
+            let spec;
+            let paren = parenthesized!(spec in input);
+            name = spec.call(Ident::parse_any).ok();
+            // attributes take two types (the arguments and the item), the others one
+            let count = if kind == "attribute" { 2 } else { 1 };
+            let typed = name.is_some() && spec.parse::<Option<Token![:]>>()?.is_some();
+            if typed {
+                while types.len() < count {
+                    if !types.is_empty() && spec.parse::<Option<Token![,]>>()?.is_none() {
+                        break;
+                    }
+                    match spec.parse::<Type>() {
+                        Ok(ty) => types.push(ty),
+                        Err(_) => break,
+                    }
+                }
+            }
+            let mut rest;
+            (rest, aliases) = trailing_options(spec.parse()?);
+            if kind == "derive" {
+                if let [TokenTree::Punct(comma), TokenTree::Ident(option), TokenTree::Group(_)] =
+                    rest.as_slice()
+                {
+                    if comma.as_char() == ',' && option == "attributes" {
+                        rest.clear();
+                    }
+                }
+            }
+            if name.is_none() || !rest.is_empty() || (typed && types.len() < count) {
+                return Err(Error::new(paren.span.join(), malformed_spec(&kind)));
+            }
+        }
+        if !input.peek(Token![->]) {
+            return Err(input.error(format!(
+                "expected `->` after `{kind}`, followed by the implementation"
+            )));
+        }
+        input.parse::<Token![->]>()?;
+
+        let mut markers = Vec::new();
+        while is_marker(input) {
+            let marker = input.call(Ident::parse_any)?;
+            if !MARKERS.contains(&marker.to_string().as_str()) {
+                return Err(Error::new(
+                    marker.span(),
+                    format!(
+                        "unknown marker `{marker}`, expected `pm2`, `try` or `catch` before the \
+                         implementation path{}",
+                        suggest::did_you_mean(&marker.to_string(), MARKERS)
+                    ),
+                ));
+            }
+            markers.push(marker);
+        }
+
+        Ok(Self {
+            attrs: Vec::new(),
+            kind,
+            name,
+            types,
+            aliases,
+            markers,
+            source: input.parse()?,
+        })
+    }
+}
+
+/// What a spec of the given kind should look like.
+fn malformed_spec(kind: &Ident) -> &'static str {
+    if kind == "function" {
+        "malformed macro name, expected `function(name)` or `function(name: InputType)`"
+    } else if kind == "attribute" {
+        "malformed macro name, expected `attribute(name)` or `attribute(name: ArgsType, \
+         ItemType)`"
+    } else {
+        "malformed derive name, expected `derive(Name)`, `derive(Name: InputType)` or either \
+         followed by `, attributes(helper, ..)`"
+    }
+}
+
+/// Splits the options the rest of a spec ends with (`, alias(name, ..)` and
+/// `, deprecated("note")`) off it, returning what precedes them and the aliases, if any.
+fn trailing_options(rest: TokenStream) -> (Vec<TokenTree>, Vec<Ident>) {
+    let mut rest = rest.into_iter().collect::<Vec<_>>();
+    let mut aliases = Vec::new();
+    while let [.., TokenTree::Punct(comma), TokenTree::Ident(option), TokenTree::Group(list)] =
+        rest.as_slice()
+    {
+        if comma.as_char() != ',' || !(option == "alias" || option == "deprecated") {
+            break;
+        }
+        if option == "alias" {
+            aliases = syn::parse::Parser::parse2(
+                Punctuated::<Ident, Token![,]>::parse_terminated,
+                list.stream(),
+            )
+            .map(|aliases| aliases.into_iter().collect())
+            .unwrap_or_default();
+        }
+        rest.truncate(rest.len() - 3);
+    }
+    (rest, aliases)
+}
+
+/// Whether the input continues with an identifier that isn't the start of a path.
+fn is_marker(input: ParseStream) -> bool {
+    if input.peek(Token![mod]) || input.peek(Token![use]) {
+        return false;
+    }
+    let fork = input.fork();
+    fork.call(Ident::parse_any).is_ok()
+        && !(fork.is_empty() || fork.peek(Token![,]) || fork.peek(Token![::]))
+}
+
+impl Parse for Source {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(LitStr) {
+            return file(input, Anchor::Source);
+        }
+        if input.parse::<Option<Token![@]>>()?.is_some() {
+            if input.peek(LitStr) && !input.peek2(Token![/]) {
+                return file(input, Anchor::Manifest);
+            }
+            let anchor = if let Some(variable) = input.parse::<Option<LitStr>>()? {
+                quote! { #variable }
+            } else {
+                let anchor = input.call(Ident::parse_any)?;
+                let written = format!("@{anchor}/");
+                if !ANCHORS.contains(&written.as_str()) {
+                    return Err(Error::new(
+                        anchor.span(),
+                        format!(
+                            "unknown implementation path anchor `{written}`, expected `@out/`, \
+                             `@workspace/`, `@glob/` or `@\"ENV_VAR\"/`{}",
+                            suggest::did_you_mean(&written, ANCHORS)
+                        ),
+                    ));
+                }
+                if anchor == "glob" {
+                    input.parse::<Token![/]>()?;
+                    return file(input, Anchor::Glob);
+                }
+                quote! { #anchor }
+            };
+            input.parse::<Token![/]>()?;
+            return file(input, Anchor::Environment(anchor));
+        }
+
+        let keyword = if input.parse::<Option<Token![use]>>()?.is_some() {
+            Some(Keyword::Use)
+        } else if input.parse::<Option<Token![mod]>>()?.is_some() {
+            Some(Keyword::Mod)
+        } else {
+            None
+        };
+
+        let mut segments = vec![input.call(Ident::parse_any)?];
+        let mut glob = None;
+        while input.parse::<Option<Token![::]>>()?.is_some() {
+            if input.parse::<Option<Token![*]>>()?.is_some() {
+                glob = Some(None);
+                break;
+            }
+            let segment = input.call(Ident::parse_any)?;
+            if input.parse::<Option<Token![*]>>()?.is_some() {
+                glob = Some(Some(segment));
+                break;
+            }
+            segments.push(segment);
+        }
+
+        Ok(Source::Path {
+            keyword,
+            segments,
+            glob,
+        })
+    }
+}
+
+/// Parses the rest of a file source, `"path" as module::function`.
+fn file(input: ParseStream, anchor: Anchor) -> Result<Source> {
+    let path = input.parse::<LitStr>()?;
+    let glob = matches!(anchor, Anchor::Glob);
+    if !glob && path.value().contains('*') {
+        return Err(Error::new(
+            path.span(),
+            "`*` is only expanded in glob paths, write `@glob/\"...\"` to register a macro per \
+             matching file",
+        ));
+    }
+    let module = match input.parse::<Option<Token![as]>>()? {
+        Some(token) if glob => {
+            return Err(Error::new(
+                token.span,
+                "a glob path declares a module per matching file, so it can't be given a \
+                 module name",
+            ))
+        },
+        Some(_) => Some(input.call(Ident::parse_any)?),
+        None => None,
+    };
+    if !input.peek(Token![::]) {
+        let message = "expected `::` and the implementing function after the path";
+        return Err(if input.is_empty() {
+            Error::new(path.span(), message)
+        } else {
+            input.error(message)
+        });
+    }
+    input.parse::<Token![::]>()?;
+
+    Ok(Source::File {
+        anchor,
+        path,
+        module,
+        function: input.call(Ident::parse_any)?,
+    })
+}
+
+/// Parses `a::b::c`.
+fn path(input: ParseStream) -> Result<Vec<Ident>> {
+    let mut segments = vec![input.call(Ident::parse_any)?];
+    while input.parse::<Option<Token![::]>>()?.is_some() {
+        segments.push(input.call(Ident::parse_any)?);
+    }
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(tokens: &str) -> Entry {
+        match syn::parse_str(tokens).unwrap() {
+            Item::Entry(entry) => entry,
+            Item::Group(_) => panic!("`{tokens}` parsed as a group"),
+        }
+    }
+
+    fn group(tokens: &str) -> Group {
+        match syn::parse_str(tokens).unwrap() {
+            Item::Group(group) => group,
+            Item::Entry(_) => panic!("`{tokens}` parsed as an entry"),
+        }
+    }
+
+    fn error(tokens: &str) -> String {
+        match syn::parse_str::<Items>(tokens) {
+            Ok(_) => panic!("`{tokens}` parsed"),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    fn names(idents: &[Ident]) -> Vec<String> {
+        idents.iter().map(Ident::to_string).collect()
+    }
+
+    /// The anchor, path, module and function of a file source.
+    fn file(tokens: &str) -> (Anchor, String, Option<String>, String) {
+        match entry(tokens).source {
+            Source::File {
+                anchor,
+                path,
+                module,
+                function,
+            } => (
+                anchor,
+                path.value(),
+                module.as_ref().map(Ident::to_string),
+                function.to_string(),
+            ),
+            Source::Path {
+                ..
+            } => panic!("`{tokens}` parsed as a path"),
+        }
+    }
+
+    #[test]
+    fn test_spec() {
+        let plain = entry("function -> shout");
+        assert_eq!(plain.kind, "function");
+        assert!(plain.name.is_none() && plain.types.is_empty() && plain.aliases.is_empty());
+
+        let named = entry("#[doc = \"\"] function(yell, alias(holler, bellow)) -> shout");
+        assert_eq!(named.attrs.len(), 1);
+        assert_eq!(named.name.unwrap(), "yell");
+        assert_eq!(names(&named.aliases), ["holler", "bellow"]);
+
+        let typed = entry("attribute(wrap: syn::Ident, syn::ItemFn, deprecated(\"no\")) -> wrap");
+        assert_eq!(typed.types.len(), 2);
+        let derive = entry("derive(Checked: syn::DeriveInput, attributes(check)) -> check");
+        assert_eq!(derive.name.unwrap(), "Checked");
+        assert_eq!(derive.types.len(), 1);
+
+        assert!(error("derives -> check").contains("(did you mean `derive`?)"));
+        assert!(error("function shout").contains("expected `->` after `function`"));
+        assert!(error("attribute(wrap item) -> wrap").contains("malformed macro name"));
+        assert!(error("attribute(wrap: syn::Ident) -> wrap").contains("malformed macro name"));
+        assert!(error("function(shout, attributes(a)) -> shout").contains("malformed"));
+        assert!(error("function -> a::f function -> g").contains("expected `,` between entries"));
+    }
+
+    #[test]
+    fn test_markers() {
+        let marked = entry("function -> try pm2 catch module::function");
+        assert_eq!(names(&marked.markers), ["try", "pm2", "catch"]);
+        let Source::Path {
+            segments,
+            ..
+        } = &marked.source
+        else {
+            panic!("the markers are followed by a path");
+        };
+        assert_eq!(names(segments), ["module", "function"]);
+
+        // paths merely starting with a marker-like identifier
+        for tokens in ["function -> pm2::function", "function -> pm2"] {
+            assert!(entry(tokens).markers.is_empty(), "{tokens}");
+        }
+
+        assert!(error("function -> uses module::function")
+            .contains("unknown marker `uses`, expected `pm2`, `try` or `catch`"));
+        assert!(error("function -> tyr module::function").contains("(did you mean `try`?)"));
+    }
+
+    #[test]
+    fn test_paths() {
+        let source = |tokens| match entry(tokens).source {
+            Source::Path {
+                keyword,
+                segments,
+                glob,
+            } => (
+                keyword,
+                names(&segments),
+                glob.map(|prefix| prefix.map(|prefix| prefix.to_string())),
+            ),
+            Source::File {
+                ..
+            } => panic!("`{tokens}` parsed as a file"),
+        };
+
+        let (keyword, segments, glob) = source("function -> a::b::f");
+        assert!(keyword.is_none() && glob.is_none());
+        assert_eq!(segments, ["a", "b", "f"]);
+        assert!(source("function -> mod a::f").0 == Some(Keyword::Mod));
+        assert!(source("function -> use a::f").0 == Some(Keyword::Use));
+        let (_, segments, glob) = source("function -> a::*");
+        assert_eq!((segments, glob), (vec!["a".to_owned()], Some(None)));
+        assert_eq!(
+            source("function -> a::to_*").2,
+            Some(Some("to_".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_anchors() {
+        let (anchor, path, module, function) = file("function -> \"hello.rs\"::hello");
+        assert!(matches!(anchor, Anchor::Source));
+        assert_eq!(
+            (path.as_str(), module, function.as_str()),
+            ("hello.rs", None, "hello")
+        );
+
+        assert!(matches!(
+            file("function -> @\"src/a.rs\"::f").0,
+            Anchor::Manifest
+        ));
+        assert!(matches!(
+            file("function -> @glob/\"src/*.rs\"::f").0,
+            Anchor::Glob
+        ));
+        for (tokens, anchor) in [
+            ("function -> @out/\"a.rs\"::f", "out"),
+            ("function -> @workspace/\"a.rs\"::f", "workspace"),
+            ("function -> @\"DIR\"/\"a.rs\"::f", "\"DIR\""),
+        ] {
+            match file(tokens).0 {
+                Anchor::Environment(written) => assert_eq!(written.to_string(), anchor),
+                _ => panic!("`{tokens}` isn't anchored in the environment"),
+            }
+        }
+
+        assert!(error("function -> @outt/\"a.rs\"::f").contains("(did you mean `@out/`?)"));
+        assert!(error("function -> \"src/*.rs\"::f").contains("write `@glob/\"...\"`"));
+        assert!(
+            error("function -> \"a.rs\"").contains("expected `::` and the implementing function")
+        );
+    }
+
+    #[test]
+    fn test_as_module() {
+        let (_, path, module, function) = file("function -> \"hello/mod.rs\" as hello_impl::hello");
+        assert_eq!(path, "hello/mod.rs");
+        assert_eq!(
+            (module.as_deref(), function.as_str()),
+            (Some("hello_impl"), "hello")
+        );
+        let (anchor, _, module, _) = file("function -> @out/\"a.rs\" as generated::f");
+        assert!(matches!(anchor, Anchor::Environment(_)));
+        assert_eq!(module.as_deref(), Some("generated"));
+
+        assert!(
+            error("function -> @glob/\"*.rs\" as m::f").contains("can't be given a module name")
+        );
+    }
+
+    #[test]
+    fn test_groups() {
+        let declared = group(
+            "#[cfg(unix)] mod strings::case { function -> lower, use inner { function -> f } }",
+        );
+        assert!(declared.declares && declared.keyword.is_some());
+        assert_eq!(declared.attrs.len(), 1);
+        assert_eq!(names(&declared.path), ["strings", "case"]);
+        assert_eq!(declared.items.0.len(), 2);
+        assert!(matches!(&declared.items.0[1], Item::Group(inner) if !inner.declares));
+
+        let implicit = group("strings { function -> length, }");
+        assert!(implicit.declares && implicit.keyword.is_none());
+
+        assert!(error("#[doc = \"\"] strings { function -> f }").contains("only `#[cfg(...)]`"));
+        assert!(error("strings { mod case { function -> f } }")
+            .contains("can only be used at the top level"));
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{bracketed, Error, LitStr, Result};
+
+/// A `@glob/"..."` `macros!` entry, as passed along by `macros!`.
+struct Entry {
+    krate: TokenStream,
+    meta: TokenStream,
+    kind: TokenStream,
+    markers: TokenStream,
+    path: LitStr,
+    function: Ident,
+}
+
+impl Parse for Entry {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let group = |input: ParseStream| -> Result<TokenStream> {
+            let content;
+            bracketed!(content in input);
+            content.parse()
+        };
+
+        Ok(Self {
+            krate: group(input)?,
+            meta: group(input)?,
+            kind: group(input)?,
+            markers: group(input)?,
+            path: input.parse()?,
+            function: input.parse()?,
+        })
+    }
+}
+
+pub fn expand(input: TokenStream) -> Result<TokenStream> {
+    let Entry {
+        krate,
+        meta,
+        kind,
+        markers,
+        path,
+        function,
+    } = syn::parse2(input)?;
+    let pattern = path.value();
+
+    let kind = match kind.into_iter().collect::<Vec<_>>().as_slice() {
+        [TokenTree::Ident(kind)] => kind.clone(),
+        _ => {
+            return Err(Error::new(
+                path.span(),
+                "entries with a glob path are named after the matching files, so they can't \
+                 be given a name (or helper attributes)",
+            ))
+        },
+    };
+
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").map_err(|_| {
+        Error::new(
+            path.span(),
+            "`CARGO_MANIFEST_DIR` isn't set, can't resolve the glob",
+        )
+    })?);
+    let files = matching_files(&root, &pattern).map_err(|err| {
+        Error::new(
+            path.span(),
+            format!("couldn't read the files matching `{pattern}`: {err}"),
+        )
+    })?;
+    if files.is_empty() {
+        return Err(Error::new(
+            path.span(),
+            format!("no files match `{pattern}`"),
+        ));
+    }
+
+    let entries = names(files, path.span())?.into_values().map(|(file, name)| {
+        let file = LitStr::new(&file, path.span());
+        quote! {
+            #krate::macros!(@emit [#meta] [#kind(#name)] [#markers @#file :: #function] [#function]);
+        }
+    });
+
+    Ok(quote! { #(#entries)* })
+}
+
+/// Names a macro after each file, keyed (and so sorted, for the expansion to be deterministic)
+/// by the name.
+fn names(files: Vec<String>, span: Span) -> Result<BTreeMap<String, (String, Ident)>> {
+    let mut entries = BTreeMap::new();
+    for file in files {
+        let stem = Path::new(&file)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default()
+            .to_owned();
+        let mut name = syn::parse_str::<Ident>(&stem).map_err(|_| {
+            Error::new(
+                span,
+                format!("`{file}` can't be registered, as `{stem}` isn't a valid macro name"),
+            )
+        })?;
+        name.set_span(span);
+        if let Some((other, _)) = entries.insert(stem.clone(), (file.clone(), name)) {
+            return Err(Error::new(
+                span,
+                format!("`{other}` and `{file}` would both register a macro named `{stem}`"),
+            ));
+        }
+    }
+    Ok(entries)
+}
+
+/// Lists the files matching `pattern` (relative to `root`, with `*` wildcards in any of its
+/// `/`-separated components), as paths relative to `root`.
+pub fn matching_files(root: &Path, pattern: &str) -> std::io::Result<Vec<String>> {
+    let components = pattern.split('/').collect::<Vec<_>>();
+    let mut matches = vec![String::new()];
+
+    for (index, component) in components.iter().enumerate() {
+        let last = index == components.len() - 1;
+        let mut next = Vec::new();
+
+        for prefix in matches {
+            if !component.contains('*') {
+                next.push(join(&prefix, component));
+                continue;
+            }
+
+            let dir = root.join(&prefix);
+            if !dir.is_dir() {
+                continue;
+            }
+            for entry in fs::read_dir(dir)? {
+                let entry = entry?;
+                let name = entry.file_name().to_string_lossy().into_owned();
+                let kind = entry.file_type()?;
+                if !name.starts_with('.')
+                    && wildcard_match(component, &name)
+                    && (if last { kind.is_file() } else { kind.is_dir() })
+                {
+                    next.push(join(&prefix, &name));
+                }
+            }
+        }
+
+        matches = next;
+    }
+
+    matches.retain(|file| root.join(file).is_file());
+    matches.sort();
+    Ok(matches)
+}
+
+fn join(prefix: &str, component: &str) -> String {
+    if prefix.is_empty() {
+        component.to_owned()
+    } else {
+        format!("{prefix}/{component}")
+    }
+}
+
+/// Matches `name` against `pattern`, where `*` stands for any (possibly empty) sequence.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == name,
+        Some((head, tail)) => {
+            name.starts_with(head)
+                && (head.len()..=name.len())
+                    .filter(|&index| name.is_char_boundary(index))
+                    .any(|index| wildcard_match(tail, &name[index..]))
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("*.rs", "upper.rs"));
+        assert!(wildcard_match("*.rs", ".rs"));
+        assert!(wildcard_match("to_*_case", "to_snake_case"));
+        assert!(wildcard_match("*", ""));
+        assert!(wildcard_match("a*b*c", "abbcbc"));
+        assert!(wildcard_match("é*", "éa"));
+        assert!(wildcard_match("plain.rs", "plain.rs"));
+        assert!(!wildcard_match("*.rs", "upper.rs.bak"));
+        assert!(!wildcard_match("to_*_case", "to_case"));
+        assert!(!wildcard_match("plain.rs", "plain.rsx"));
+    }
+
+    #[test]
+    fn test_matching_files() {
+        let root = env::temp_dir().join(format!("include_proc_macro_glob_{}", std::process::id()));
+        for file in [
+            "macros/fn/sum.rs",
+            "macros/fn/product.rs",
+            "macros/fn/notes.txt",
+            "macros/fn/.hidden.rs",
+            "macros/attr/wrap.rs",
+            "macros/dir.rs/inner.rs",
+            "other/fn/ignored.rs",
+        ] {
+            let file = root.join(file);
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, "").unwrap();
+        }
+
+        let matches = |pattern| matching_files(&root, pattern).unwrap();
+        assert_eq!(
+            matches("macros/fn/*.rs"),
+            ["macros/fn/product.rs", "macros/fn/sum.rs"]
+        );
+        assert_eq!(
+            matches("macros/*/*.rs"),
+            [
+                "macros/attr/wrap.rs",
+                "macros/dir.rs/inner.rs",
+                "macros/fn/product.rs",
+                "macros/fn/sum.rs",
+            ]
+        );
+        // directories don't match the last component, nor files the others
+        assert_eq!(matches("macros/*"), Vec::<String>::new());
+        assert_eq!(matches("*/fn/s*.rs"), ["macros/fn/sum.rs"]);
+        assert_eq!(matches("macros/fn/sum.rs"), ["macros/fn/sum.rs"]);
+        assert_eq!(matches("missing/*.rs"), Vec::<String>::new());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_names() {
+        let files = |files: &[&str]| files.iter().map(|file| file.to_string()).collect();
+        let sorted = names(files(&["fn/sum.rs", "fn/product.rs"]), Span::call_site()).unwrap();
+        assert_eq!(sorted.keys().collect::<Vec<_>>(), ["product", "sum"]);
+        assert_eq!(sorted["sum"].0, "fn/sum.rs");
+
+        let duplicate = names(files(&["fn/upper.rs", "attr/upper.rs"]), Span::call_site());
+        assert_eq!(
+            duplicate.unwrap_err().to_string(),
+            "`fn/upper.rs` and `attr/upper.rs` would both register a macro named `upper`"
+        );
+        let invalid = names(files(&["fn/to-upper.rs"]), Span::call_site());
+        assert_eq!(
+            invalid.unwrap_err().to_string(),
+            "`fn/to-upper.rs` can't be registered, as `to-upper` isn't a valid macro name"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! Companion proc macros of [`include_proc_macro`](https://docs.rs/include_proc_macro), for the
+//! parts of its grammar that `macro_rules` can't express, such as looking at the file system.
+//!
+//! Not meant to be used directly: with the `frontend` feature of `include_proc_macro`, `macros!`
+//! invocations are parsed and checked here first (reporting every malformed entry and missing
+//! module, file or function, with suggestions), and the relevant entries are routed here later
+//! on, to be expanded back into `macros!` entries. The entry points of derives are also renamed
+//! here, as `macro_rules` can't build identifiers.
+
+extern crate proc_macro;
+
+mod bulk;
+mod check;
+mod dsl;
+mod glob;
+mod manifest;
+mod suggest;
+
+use std::env;
+use std::path::Path;
+
+use proc_macro::{Group, Span, TokenStream, TokenTree};
+use quote::format_ident;
+use syn::parse::{Parse, ParseStream};
+use syn::{bracketed, Ident, ItemFn, LitStr};
+
+/// Parses and checks a whole `macros!` invocation, before handing it back to `macros!`.
+///
+/// Takes `[$crate] entries`.
+#[proc_macro]
+pub fn macros(input: TokenStream) -> TokenStream {
+    dsl::expand(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Expands a crate-relative (`@"..."`) `macros!` entry, registering one macro per matching file
+/// (named after the file stem) if the path contains `*` wildcards.
+///
+/// Takes `[$crate] [attributes] [kind] [markers] "path" function`.
+#[proc_macro]
+pub fn glob(input: TokenStream) -> TokenStream {
+    glob::expand(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Expands a `module::*` `macros!` entry, registering one macro per public function of the
+/// module (named after the function), optionally only those starting with a prefix.
+///
+/// Takes `[$crate] [attributes] [kind] [markers] [module path] [prefix]`.
+#[proc_macro]
+pub fn bulk(input: TokenStream) -> TokenStream {
+    bulk::expand(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Declares a file relative to the manifest directory (`@"..."`) as the `__file` module, at the
+/// absolute path, since `#[path]` is relative to the file it's in (wherever the crate root is).
+///
+/// Takes `"path"`.
+#[proc_macro]
+pub fn crate_file(input: TokenStream) -> TokenStream {
+    let path = syn::parse_macro_input!(input as LitStr);
+    let Ok(manifest) = env::var("CARGO_MANIFEST_DIR") else {
+        return syn::Error::new(path.span(), "`@` paths need `CARGO_MANIFEST_DIR` to be set")
+            .into_compile_error()
+            .into();
+    };
+    let file = Path::new(&manifest).join(path.value());
+    let file = LitStr::new(&file.to_string_lossy(), path.span());
+
+    // the same file may be declared by several entries
+    quote::quote!(#[allow(clippy::duplicate_mod)] #[path = #file] pub mod __file;).into()
+}
+
+/// The root of the workspace (`@workspace/`), as a string literal, so that it's found without
+/// `CARGO_WORKSPACE_DIR` being set up (which still overrides it).
+///
+/// Takes nothing.
+#[proc_macro]
+pub fn workspace_dir(_: TokenStream) -> TokenStream {
+    let Some(workspace) = manifest::workspace() else {
+        return syn::Error::new(
+            Span::call_site().into(),
+            "`@workspace/` paths need `CARGO_MANIFEST_DIR` to be set",
+        )
+        .into_compile_error()
+        .into();
+    };
+    let workspace = LitStr::new(&workspace.to_string_lossy(), Span::call_site().into());
+    quote::quote!(#workspace).into()
+}
+
+/// Moves tokens onto the last token of an entry's implementation (its function), so that the
+/// errors they raise point at it rather than at the whole `macros!` invocation. Where the tokens
+/// resolve is left as is.
+///
+/// Takes `[implementation] tokens`.
+#[proc_macro]
+pub fn respan(input: TokenStream) -> TokenStream {
+    fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+        tokens
+            .into_iter()
+            .map(|mut token| {
+                if let TokenTree::Group(group) = &token {
+                    let mut inner = Group::new(group.delimiter(), respan(group.stream(), span));
+                    inner.set_span(group.span().located_at(span));
+                    token = inner.into();
+                } else {
+                    token.set_span(token.span().located_at(span));
+                }
+                token
+            })
+            .collect()
+    }
+
+    let mut tokens = input.into_iter();
+    let span = match tokens.next() {
+        Some(TokenTree::Group(implementation)) => implementation.stream().into_iter().last(),
+        _ => None,
+    };
+    match span {
+        Some(last) => respan(tokens.collect(), last.span()),
+        None => tokens.collect(),
+    }
+}
+
+/// Renames the entry point of a macro to its name with a prefix, e.g. `__derive_Validate`.
+///
+/// Takes `[prefix] entry point`.
+#[proc_macro]
+pub fn entry_point(input: TokenStream) -> TokenStream {
+    struct EntryPoint {
+        prefix: Ident,
+        function: ItemFn,
+    }
+
+    impl Parse for EntryPoint {
+        fn parse(input: ParseStream) -> syn::Result<Self> {
+            let prefix;
+            bracketed!(prefix in input);
+            Ok(Self {
+                prefix: prefix.parse()?,
+                function: input.parse()?,
+            })
+        }
+    }
+
+    let EntryPoint {
+        prefix,
+        mut function,
+    } = syn::parse_macro_input!(input);
+    let name = &function.sig.ident;
+    function.sig.ident = format_ident!("{prefix}{name}", span = name.span());
+    quote::quote!(#function).into()
+}
@@ -0,0 +1,150 @@
+//! Locates the crate being compiled: its manifest directory, its root file (which its
+//! modules, and plain file paths, are relative to), and the root of its workspace.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The manifest directory and root file of the crate being compiled.
+pub struct Crate {
+    pub manifest: PathBuf,
+    pub root: PathBuf,
+}
+
+impl Crate {
+    /// The crate being compiled, if its root file can be found: the `[lib] path` of its
+    /// manifest, `src/lib.rs` by default. Whatever depends on it isn't checked otherwise.
+    pub fn current() -> Option<Self> {
+        let manifest = PathBuf::from(env::var("CARGO_MANIFEST_DIR").ok()?);
+        let source = fs::read_to_string(manifest.join("Cargo.toml")).ok()?;
+        let root = manifest.join(lib_path(&source)?);
+
+        root.is_file().then_some(Self {
+            manifest,
+            root,
+        })
+    }
+
+    /// The directory the modules declared in the root file live in.
+    pub fn modules(&self) -> &Path {
+        self.root.parent().unwrap_or(&self.manifest)
+    }
+
+    /// A path of the crate as it'd be written in its manifest, e.g. `src/foo.rs`.
+    pub fn relative(&self, path: &Path) -> String {
+        path.strip_prefix(&self.manifest)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+}
+
+/// The root of the workspace the crate being compiled is in: `CARGO_WORKSPACE_DIR` if it's
+/// set, or else the closest directory from the manifest directory up whose manifest has a
+/// `[workspace]` table, the manifest directory itself if none has (as cargo doesn't tell).
+pub fn workspace() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("CARGO_WORKSPACE_DIR") {
+        return Some(dir.into());
+    }
+    let manifest = PathBuf::from(env::var("CARGO_MANIFEST_DIR").ok()?);
+    let root = manifest.ancestors().find(|dir| {
+        fs::read_to_string(dir.join("Cargo.toml")).is_ok_and(|source| is_workspace(&source))
+    });
+
+    Some(root.unwrap_or(&manifest).to_path_buf())
+}
+
+/// Whether a manifest has a `[workspace]` table, or any of its subtables.
+fn is_workspace(manifest: &str) -> bool {
+    manifest
+        .lines()
+        .filter_map(header)
+        .any(|table| table == "workspace" || table.starts_with("workspace."))
+}
+
+/// The name of the table a line starts, without its brackets.
+fn header(line: &str) -> Option<&str> {
+    let header = line.trim().strip_prefix('[')?;
+    let header = header.split('#').next().unwrap_or_default();
+    Some(header.trim().trim_matches(['[', ']']).trim())
+}
+
+/// The `[lib] path` of a manifest, `src/lib.rs` if it has none, or `None` if it's written in
+/// a way this doesn't read (e.g. an inline `lib = { .. }` table).
+fn lib_path(manifest: &str) -> Option<String> {
+    let mut table = String::new();
+    for line in manifest.lines().map(str::trim) {
+        if let Some(header) = header(line) {
+            table = header.to_owned();
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match (table.as_str(), key.trim()) {
+            ("lib", "path") | ("", "lib.path") => return string(value),
+            ("", "lib") => return None,
+            _ => {},
+        }
+    }
+    Some("src/lib.rs".to_owned())
+}
+
+/// The value of a basic or literal string without escapes, followed by a comment at most.
+fn string(value: &str) -> Option<String> {
+    let value = value.trim();
+    let quote = value
+        .chars()
+        .next()
+        .filter(|quote| ['"', '\''].contains(quote))?;
+    let (string, rest) = value[1..].split_once(quote)?;
+    let rest = rest.trim();
+    (!string.contains('\\') && (rest.is_empty() || rest.starts_with('#')))
+        .then(|| string.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lib_path() {
+        assert_eq!(
+            lib_path("[package]\nname = \"a\"\n").as_deref(),
+            Some("src/lib.rs")
+        );
+        assert_eq!(
+            lib_path("[lib]\nproc-macro = true\npath = \"lib.rs\" # root\n").as_deref(),
+            Some("lib.rs")
+        );
+        assert_eq!(
+            lib_path("[lib] # the root\npath = 'macros.rs'").as_deref(),
+            Some("macros.rs")
+        );
+        assert_eq!(
+            lib_path("lib.path = \"root.rs\"").as_deref(),
+            Some("root.rs")
+        );
+        assert_eq!(
+            lib_path("[dependencies]\npath = \"a\"\n[lib]\nproc-macro = true").as_deref(),
+            Some("src/lib.rs")
+        );
+        assert_eq!(lib_path("lib = { path = \"lib.rs\" }"), None);
+        assert_eq!(lib_path("[lib]\npath = \"a\\\\lib.rs\""), None);
+    }
+
+    #[test]
+    fn test_is_workspace() {
+        assert!(is_workspace("[workspace]\nmembers = [\"a\"]\n"));
+        assert!(is_workspace(
+            "[package]\nname = \"a\"\n\n[workspace] # the root\n"
+        ));
+        assert!(is_workspace("[workspace.dependencies]\nsyn = \"2\"\n"));
+        assert!(!is_workspace(
+            "[package]\nname = \"a\"\nworkspace = \"..\"\n"
+        ));
+        assert!(!is_workspace(
+            "[workspaces]\n[dependencies]\nworkspace = \"1\"\n"
+        ));
+    }
+}
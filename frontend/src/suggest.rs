@@ -0,0 +1,76 @@
+//! "Did you mean" suggestions for misspelled names.
+
+/// ` (did you mean `candidate`?)` for the closest candidate to `name`, if any is close enough.
+pub fn did_you_mean(name: &str, candidates: &[impl AsRef<str>]) -> String {
+    closest(name, candidates)
+        .map(|candidate| format!(" (did you mean `{candidate}`?)"))
+        .unwrap_or_default()
+}
+
+/// The closest candidate to `name`, within a third of their length (and at least one edit).
+fn closest<'a>(name: &str, candidates: &'a [impl AsRef<str>]) -> Option<&'a str> {
+    candidates
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (distance(name, candidate), candidate))
+        .filter(|(distance, candidate)| {
+            *distance <= (name.chars().count().max(candidate.chars().count()) / 3).max(1)
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// The edit distance between two strings, counting transpositions of adjacent characters as a
+/// single edit.
+fn distance(a: &str, b: &str) -> usize {
+    let (a, b) = (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>());
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance("", ""), 0);
+        assert_eq!(distance("function", "function"), 0);
+        assert_eq!(distance("fucntion", "function"), 1);
+        assert_eq!(distance("tyr", "try"), 1);
+        assert_eq!(distance("derives", "derive"), 1);
+        assert_eq!(distance("", "pm2"), 3);
+    }
+
+    #[test]
+    fn test_did_you_mean() {
+        let kinds = ["function", "attribute", "derive"];
+        assert_eq!(did_you_mean("derives", &kinds), " (did you mean `derive`?)");
+        assert_eq!(
+            did_you_mean("atribute", &kinds),
+            " (did you mean `attribute`?)"
+        );
+        assert_eq!(did_you_mean("struct", &kinds), "");
+        assert_eq!(did_you_mean("function", &kinds), "");
+    }
+}
@@ -0,0 +1,49 @@
+use std::{env, fmt::Write, fs, path::Path};
+
+// only the names of the entries are needed here
+#[allow(dead_code)]
+#[path = "../examples/build/matrix.rs"]
+mod matrix;
+
+fn main() {
+    println!("cargo:rerun-if-changed=../examples/build/matrix.rs");
+
+    // one test per implementation source, covering each of its entries
+    let mut tests = String::new();
+    let mut source = "";
+    let frontend = env::var_os("CARGO_FEATURE_FRONTEND").is_some();
+    for (index, entry) in matrix::entries(frontend).enumerate() {
+        if entry.source != source {
+            if !source.is_empty() {
+                tests.push_str("}\n");
+            }
+            source = entry.source;
+            writeln!(tests, "\n#[test]\nfn test_{source}_matrix() {{").unwrap();
+        }
+
+        let name = entry.name();
+        let expected = format!("{source} {}", entry.kind);
+        match entry.kind {
+            "function" => writeln!(tests, "    assert_eq!(examples::{name}!(), {expected:?});"),
+            "attribute" => writeln!(
+                tests,
+                "    #[examples::{name}]\n    fn attribute_{index}() -> &'static str {{ \"\" }}\n    \
+                 assert_eq!(attribute_{index}(), {expected:?});"
+            ),
+            _ => writeln!(
+                tests,
+                "    #[derive(examples::{name})]{}\n    struct Derive{index};\n    \
+                 assert_eq!(Derive{index}::SOURCE, {expected:?});",
+                if entry.naming == "helpers" { "\n    #[matrix]" } else { "" }
+            ),
+        }
+        .unwrap();
+    }
+    tests.push_str("}\n");
+
+    fs::write(
+        Path::new(&env::var("OUT_DIR").unwrap()).join("matrix.rs"),
+        tests,
+    )
+    .unwrap();
+}
@@ -0,0 +1,9 @@
+use proc_macro::TokenStream;
+
+pub fn upper(input: TokenStream) -> TokenStream {
+    input.to_string().to_uppercase().parse().unwrap()
+}
+
+pub fn lower(input: TokenStream) -> TokenStream {
+    input.to_string().to_lowercase().parse().unwrap()
+}
@@ -0,0 +1,5 @@
+use proc_macro::TokenStream;
+
+pub fn goodbye(_input: TokenStream) -> TokenStream {
+    "\"Goodbye\"".parse().unwrap()
+}
@@ -0,0 +1,5 @@
+use proc_macro::TokenStream;
+
+pub fn hello(_input: TokenStream) -> TokenStream {
+    "\"Hello\"".parse().unwrap()
+}
@@ -0,0 +1,10 @@
+//! Implementations relative to the manifest directory, and modules relative to the crate root,
+//! from a crate root that isn't in `src/`.
+
+include_proc_macro::macros!(
+    function -> @"impls/hello.rs"::hello,
+    function -> @"impls/goodbye.rs" as farewells::goodbye,
+    function -> shouts::shout,
+    #[cfg(feature = "frontend")]
+    function -> cases::*,
+);
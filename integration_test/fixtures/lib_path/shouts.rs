@@ -0,0 +1,5 @@
+use proc_macro::TokenStream;
+
+pub fn shout(input: TokenStream) -> TokenStream {
+    input.to_string().to_uppercase().parse().unwrap()
+}
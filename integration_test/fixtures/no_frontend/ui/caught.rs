@@ -0,0 +1,8 @@
+//! A panic in a `catch` implementation is reported as an error naming the macro, and its
+//! implementation as the entry wrote it.
+
+use examples::single_literal;
+
+fn main() {
+    let _ = single_literal!(1 2);
+}
@@ -0,0 +1,18 @@
+//! Without the frontend, every malformed entry is reported on its own, along with the forms
+//! entries can take, and the ones after it are still expanded.
+
+include_proc_macro::macros! {
+    derives(Checked) -> checks::derive_checked,
+    function shout -> shouts::shout,
+    function(shout) shouts::shout,
+    attribute(wrap item) -> wraps::wrap,
+    derive -> uses checks::derive_checked,
+    function -> "shouts.rs",
+    use shouts {
+        mod inner {
+            function -> whisper,
+        },
+    },
+}
+
+fn main() {}
@@ -0,0 +1 @@
+pub use crate::wrong::{shout, wrap};
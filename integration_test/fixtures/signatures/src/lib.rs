@@ -0,0 +1,9 @@
+//! Implementations whose signature doesn't fit their entry, re-exported so that the frontend
+//! can't see them: the signature check reports them, and doesn't let the calls compile.
+
+mod wrong;
+
+include_proc_macro::macros!(
+    function -> imp::shout,
+    attribute -> imp::wrap,
+);
@@ -0,0 +1,9 @@
+use proc_macro::TokenStream;
+
+pub fn shout(input: TokenStream) -> String {
+    input.to_string().to_uppercase()
+}
+
+pub fn wrap(item: TokenStream) -> TokenStream {
+    item
+}
@@ -1,15 +1,29 @@
 #[cfg(test)]
+// the tests predate the clippy gate, and are kept as written
+#[allow(dead_code, unused_variables, clippy::bool_assert_comparison)]
 mod tests {
+    #[cfg(feature = "frontend")]
+    use std::path::Path;
+    #[cfg(feature = "frontend")]
+    use std::process::{Command, Output};
+
     use examples::{
-        derive_debug, fizz, generate_documentation, greet, DefaultImpl, DisplayImpl,
+        count_idents, count_tokens, derive_debug, document, double, farewell, fizz,
+        generate_documentation, greet, hello, hi, legacy_counter, legacy_getters, length, lower,
+        ofo, rename, rename_fn, repeat, salute, shout, single_literal, version, DefaultImpl,
         NodeTypeChecks, Validate,
     };
+    #[cfg(feature = "frontend")]
+    use examples::{
+        product, sum, to_kebab_case, to_snake_case, Describe, DisplayImpl, LegacyDisplay,
+    };
 
     #[test]
     fn test_function_macros() {
         // greet
         let result = greet!("World");
         assert_eq!(result, "Hello, World");
+        assert_eq!(farewell!("World"), "Goodbye, World");
 
         // fizzbuzz
         assert_eq!(fizz!(3), "Fizz");
@@ -18,6 +32,214 @@ mod tests {
         assert_eq!(fizz!(7), 7);
     }
 
+    #[test]
+    fn test_aliased_macros() {
+        assert_eq!(hello!("World"), greet!("World"));
+        assert_eq!(hi!("World"), "Hello, World");
+
+        #[rename_fn(renamed)]
+        fn original() -> u8 {
+            7
+        }
+        assert_eq!(renamed(), 7);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_macros() {
+        assert_eq!(salute!("World"), "Hello, World");
+
+        // the output of these could be associated items, so the reference to the deprecated
+        // item goes into a function body
+        struct TestMethods;
+        impl TestMethods {
+            legacy_getters!(first second);
+
+            #[document(documented)]
+            fn documented(&self) -> u8 {
+                7
+            }
+        }
+        assert_eq!(TestMethods.first(), "first");
+        assert_eq!(TestMethods.second(), "second");
+        assert_eq!(TestMethods.documented(), 7);
+
+        // the output of a macro call could be in any position, so it's left as is
+        legacy_counter!(COUNTER);
+        COUNTER.with(|counter| counter.set(counter.get() + 1));
+        assert_eq!(COUNTER.with(|counter| counter.get()), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "frontend")]
+    fn test_lib_path_fixture() {
+        // with the frontend the file is declared as a module, without it it's included
+        build_fixture("lib_path", &[]);
+        build_fixture("lib_path", &["--no-default-features"]);
+    }
+
+    #[test]
+    #[cfg(feature = "frontend")]
+    fn test_signatures_fixture() {
+        // the checks replace the calls, so that they're the only errors. with the frontend,
+        // they point at the implementations of the entries rather than the whole invocation
+        for (args, shout, wrap) in [
+            (&[][..], "src/lib.rs:7:22", "src/lib.rs:8:23"),
+            (
+                &["--no-default-features"][..],
+                "src/lib.rs:6:1",
+                "src/lib.rs:6:1",
+            ),
+        ] {
+            let output = fixture("build", "signatures", args);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let reported = |message: &str, location: &str| {
+                let mut lines = stderr.lines().skip_while(|line| !line.ends_with(message));
+                lines.nth(1).map(str::trim) == Some(&format!("--> {location}"))
+            };
+            assert!(
+                !output.status.success(),
+                "Fixture `signatures` built with {args:?}"
+            );
+            assert!(
+                reported(
+                    "function-like implementations must be `fn(TokenStream) -> TokenStream`",
+                    shout
+                ),
+                "{stderr}"
+            );
+            assert!(
+                reported(
+                    "attribute implementations must be `fn(TokenStream, TokenStream) -> TokenStream`",
+                    wrap
+                ),
+                "{stderr}"
+            );
+            assert!(stderr.contains("due to 2 previous errors"), "{stderr}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "frontend")]
+    fn test_no_frontend_fixture() {
+        // the tests of this crate, against examples built without the frontend, for the entries
+        // that don't need it (the matrix, groups, cfgs and modules shared between entries), and
+        // its own UI tests. the other fixtures aren't built again there, nor are the globs and
+        // `module::*` entries tested
+        let output = fixture("test", "no_frontend", &[]);
+        assert!(
+            output.status.success(),
+            "Fixture `no_frontend` failed its tests:\n{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    #[cfg(feature = "frontend")]
+    /// Builds the crate in `fixtures/`, which has a manifest of its own (e.g. to lay out its
+    /// files differently).
+    fn build_fixture(name: &str, args: &[&str]) {
+        let output = fixture("build", name, args);
+        assert!(
+            output.status.success(),
+            "Fixture `{name}` failed to build with {args:?}:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    #[cfg(feature = "frontend")]
+    fn fixture(command: &str, name: &str, args: &[&str]) -> Output {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        Command::new("cargo")
+            .arg(command)
+            .args(args)
+            .arg("--manifest-path")
+            .arg(root.join("fixtures").join(name).join("Cargo.toml"))
+            .env("CARGO_TARGET_DIR", root.join("../target/fixtures"))
+            .output()
+            .expect("Failed to run cargo on the fixture")
+    }
+
+    #[test]
+    #[cfg(feature = "frontend")]
+    fn test_diagnostics() {
+        let cases = trybuild::TestCases::new();
+        cases.compile_fail("ui/*.rs");
+    }
+
+    // only run in `fixtures/no_frontend`, so relative to it, for the errors `macros!` reports
+    // without the frontend
+    #[test]
+    #[cfg(not(feature = "frontend"))]
+    fn test_diagnostics_without_frontend() {
+        let cases = trybuild::TestCases::new();
+        cases.compile_fail("ui/*.rs");
+    }
+
+    #[test]
+    fn test_pm2_function_macro() {
+        assert_eq!(shout!("hello"), "HELLO");
+    }
+
+    #[test]
+    fn test_shared_module_function_macros() {
+        mod baz {
+            examples::bar!();
+        }
+        mod ofo {
+            super::ofo!();
+        }
+
+        assert_eq!(baz::bar(), "baz");
+        assert_eq!(ofo::bar(), "ofo");
+    }
+
+    #[test]
+    fn test_generated_function_macro() {
+        assert_eq!(version!(), "0.1.0");
+    }
+
+    #[test]
+    #[cfg(feature = "frontend")]
+    fn test_glob_function_macros() {
+        assert_eq!(sum!(1, 2, 3), 6);
+        assert_eq!(product!(2, 3, 4), 24);
+    }
+
+    #[test]
+    #[cfg(feature = "frontend")]
+    fn test_bulk_function_macros() {
+        assert_eq!(to_snake_case!("HelloWorld"), "hello_world");
+        assert_eq!(to_kebab_case!("HelloWorld"), "hello-world");
+    }
+
+    #[test]
+    fn test_anchored_function_macros() {
+        assert_eq!(count_tokens!(a + (b c) d), 4);
+        assert_eq!(count_idents!(a + (b c) d), 2);
+    }
+
+    #[test]
+    fn test_grouped_function_macros() {
+        assert_eq!(length!("héllo"), 5);
+        assert_eq!(lower!("HeLLo"), "hello");
+    }
+
+    #[test]
+    fn test_typed_function_macro() {
+        assert_eq!(repeat!("ab", 3), "ababab");
+    }
+
+    #[test]
+    fn test_try_function_macro() {
+        assert_eq!(double!(21), 42);
+    }
+
+    #[test]
+    fn test_catch_function_macro() {
+        assert_eq!(single_literal!("caught"), "caught");
+    }
+
     #[test]
     fn test_derive_debug_attr_macro() {
         #[derive_debug]
@@ -48,6 +270,18 @@ mod tests {
     }
 
     #[test]
+    fn test_typed_attr_macro() {
+        #[rename(renamed)]
+        fn original() -> u8 {
+            7
+        }
+
+        assert_eq!(renamed(), 7);
+    }
+
+    #[test]
+    #[cfg(feature = "frontend")]
+    #[allow(deprecated)]
     fn test_derive_display_macros() {
         #[derive(DisplayImpl)]
         struct TestDisplay;
@@ -63,6 +297,14 @@ mod tests {
 
         let test_enum = TestEnum::A;
         assert_eq!(format!("{}", test_enum), "This is a TestEnum");
+
+        #[derive(Describe)]
+        struct TestDescribe;
+        assert_eq!(format!("{}", TestDescribe), "This is a TestDescribe");
+
+        #[derive(LegacyDisplay)]
+        struct TestLegacy;
+        assert_eq!(format!("{}", TestLegacy), "This is a TestLegacy");
     }
 
     #[test]
@@ -153,4 +395,7 @@ mod tests {
         };
         assert!(invalid_age.validate().is_err());
     }
+
+    // one test per implementation source of the matrix of entries, generated by the build script
+    include!(concat!(env!("OUT_DIR"), "/matrix.rs"));
 }
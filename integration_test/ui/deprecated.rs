@@ -0,0 +1,28 @@
+//! Every deprecated macro warns where it's used, whatever position its output ends up in.
+#![deny(deprecated)]
+
+use examples::{document, legacy_bindings, legacy_getters, salute, LegacyDisplay};
+
+#[derive(LegacyDisplay)]
+struct Derived;
+
+struct Methods;
+
+impl Methods {
+    legacy_getters!(first second);
+
+    #[document(documented)]
+    fn documented(&self) -> u8 {
+        7
+    }
+}
+
+#[document(documented)]
+fn documented() {}
+
+legacy_bindings!(fn abs(x: i32) -> i32;);
+
+fn main() {
+    let _ = (salute!("World"), Derived, Methods.documented(), documented());
+    let _ = unsafe { abs(-7) };
+}
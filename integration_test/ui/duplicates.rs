@@ -0,0 +1,18 @@
+//! Entries registering macros of the same name are reported, unless their cfgs may exclude
+//! each other.
+
+include_proc_macro::macros! {
+    function -> hello::greet,
+    #[cfg(feature = "loud")]
+    function(greet) -> shouts::shout,
+    #[cfg(feature = "loud")]
+    function(shout) -> shouts::shout,
+    #[cfg(all(feature = "loud", unix))]
+    function(shout) -> shouts::whisper,
+    #[cfg(feature = "quiet")]
+    function(whisper) -> shouts::shout,
+    #[cfg(feature = "loud")]
+    function(whisper) -> shouts::whisper,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+//! `*` is only expanded in `@glob/` paths, which register a macro per matching file.
+
+include_proc_macro::macros! {
+    function -> @"src/macros/*.rs"::expand,
+}
+
+include_proc_macro::macros! {
+    function -> @glob/"src/macros/*.rs" as macros::expand,
+}
+
+include_proc_macro::macros! {
+    function -> @glob/"missing/*.rs"::expand,
+}
+
+fn main() {}
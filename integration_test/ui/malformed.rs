@@ -0,0 +1,35 @@
+//! With the frontend, malformed entries are reported at the offending tokens.
+
+include_proc_macro::macros! {
+    derives(Checked) -> checks::derive_checked,
+}
+
+include_proc_macro::macros! {
+    function shout -> shouts::shout,
+}
+
+include_proc_macro::macros! {
+    function(shout) shouts::shout,
+}
+
+include_proc_macro::macros! {
+    attribute(wrap item) -> wraps::wrap,
+}
+
+include_proc_macro::macros! {
+    derive -> uses checks::derive_checked,
+}
+
+include_proc_macro::macros! {
+    function -> "shouts.rs",
+}
+
+include_proc_macro::macros! {
+    use shouts {
+        mod inner {
+            function -> whisper,
+        },
+    },
+}
+
+fn main() {}
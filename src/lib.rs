@@ -1,5 +1,9 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", "README.md"))]
 
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
 /// Defines and delegates a function-like procedural macro from another module.
 ///
 /// ## Usage patterns:
@@ -8,79 +12,34 @@
 /// - Nested modules: `proc_macro!(name -> a::b::c::function)`
 /// - Literal path: `proc_macro!(name -> "path/to/file.rs"::function)`
 /// - Crate-relative path: `proc_macro!(name -> @"path/from/crate/root.rs"::function)`
+/// - Build script output (relative to `OUT_DIR`): `proc_macro!(name -> @out/"generated.rs"::function)`
+/// - Workspace-relative path: `proc_macro!(name -> @workspace/"shared/impl.rs"::function)`
+/// - Path relative to an environment variable: `proc_macro!(name -> @"ENV_VAR"/"impl.rs"::function)`
+/// - Path declared at the crate root as a named module: `proc_macro!(name -> "path/to/file.rs" as module::function)`
+/// - With doc comments and other attributes: `proc_macro!(#[doc = "..."] name -> function)`
+/// - Behind a cfg (also gating the module declaration): `proc_macro!(#[cfg(feature = "x")] name -> module::function)`
+/// - `proc_macro2` implementation: `proc_macro!(name -> pm2 module::function)`
+/// - Fallible implementation: `proc_macro!(name -> try module::function)`
+/// - Panics reported as errors: `proc_macro!(name -> catch module::function)`
+/// - Parsed input: `proc_macro!((name: InputType) -> module::function)`
+/// - Aliases, invoking the same implementation: `proc_macro!((name, alias(old, other)) -> module::function)`
+/// - Deprecated, warning where it's invoked: `proc_macro!((name, deprecated("use `other!` instead")) -> module::function)`
 ///
 /// See: [`macros!`](crate::macros)
 #[macro_export]
 macro_rules! proc_macro {
-    // base implementation for direct function reference with explicit use keyword
-    ($name:ident ->  use $func:ident) => {
-        #[proc_macro]
-        pub fn $name(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-            $func(input)
-        }
-    };
-    // shorthand that delegates to module pattern
-    ($name:ident -> $func:ident) => {
-        $crate::proc_macro!($name -> mod $func);
-    };
-
-    // base implementation for using existing modules
-    ($name:ident -> use $module:ident :: $func:ident) => {
-        #[proc_macro]
-        pub fn $name(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-            $module::$func(input)
-        }
-    };
-
-    // declares module explicitly and delegates to use variant
-    ($name:ident -> mod $module:ident :: $func:ident) => {
-        mod $module;
-        $crate::proc_macro!($name -> use $module :: $func);
-    };
-
-    // implicit module (defaults to explicit module declaration)
-    ($name:ident -> $module:ident :: $func:ident) => {
-        $crate::proc_macro!($name -> mod $module :: $func);
-    };
-
-    // base implementation for nested modules with existing imports
-    ($name:ident -> use $($module:ident)::+ :: $func:ident) => {
-        #[proc_macro]
-        pub fn $name(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-            $($module::)+$func(input)
-        }
-    };
-
-    // declares root module and delegates to use variant for nested modules
-    ($name:ident -> mod $first:ident $(:: $rest:ident)+ :: $func:ident) => {
-        mod $first;
-        $crate::proc_macro!($name -> use $first$(::$rest)+ :: $func);
-    };
-
-    // implicit nested modules (defaults to explicit module declaration)
-    ($name:ident -> $first:ident $(:: $rest:ident)+ :: $func:ident) => {
-        $crate::proc_macro!($name -> mod $first $(:: $rest)+ :: $func);
+    ($(#[$($meta:tt)*])* ($name:ident : $input:ty $(, alias($($alias:ident),* $(,)?))? $(, deprecated($note:literal))?) -> $($impl:tt)+) => {
+        $crate::__delegate!(
+            @attrs [function $name [$($($alias)*)?] [$($note)?]] [[$input]] [] [] [$(#[$($meta)*])*] $($impl)+
+        );
     };
-
-    // base implementation for literal file paths
-    ($name:ident -> $path:literal :: $func:ident) => {
-        #[proc_macro]
-        pub fn $name(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-            #[path = $path]
-            mod __inner;
-            __inner::$func(input)
-        }
+    ($(#[$($meta:tt)*])* ($name:ident $(, alias($($alias:ident),* $(,)?))? $(, deprecated($note:literal))?) -> $($impl:tt)+) => {
+        $crate::__delegate!(
+            @attrs [function $name [$($($alias)*)?] [$($note)?]] [] [] [] [$(#[$($meta)*])*] $($impl)+
+        );
     };
-
-    // base implementation for crate-relative paths (prefixed with @)
-    ($name:ident -> @$path:literal :: $func:ident) => {
-        #[proc_macro]
-        pub fn $name(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-            mod __inner {
-                include!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $path));
-            }
-            __inner::$func(input)
-        }
+    ($(#[$($meta:tt)*])* $name:ident -> $($impl:tt)+) => {
+        $crate::__delegate!(@attrs [function $name [] []] [] [] [] [$(#[$($meta)*])*] $($impl)+);
     };
 }
 
@@ -92,75 +51,34 @@ macro_rules! proc_macro {
 /// - Nested modules: `attr_macro!(name -> a::b::c::function)`
 /// - Literal path: `attr_macro!(name -> "path/to/file.rs"::function)`
 /// - Crate-relative path: `attr_macro!(name -> @"path/from/crate/root.rs"::function)`
+/// - Build script output (relative to `OUT_DIR`): `attr_macro!(name -> @out/"generated.rs"::function)`
+/// - Workspace-relative path: `attr_macro!(name -> @workspace/"shared/impl.rs"::function)`
+/// - Path relative to an environment variable: `attr_macro!(name -> @"ENV_VAR"/"impl.rs"::function)`
+/// - Path declared at the crate root as a named module: `attr_macro!(name -> "path/to/file.rs" as module::function)`
+/// - With doc comments and other attributes: `attr_macro!(#[doc = "..."] name -> function)`
+/// - Behind a cfg (also gating the module declaration): `attr_macro!(#[cfg(feature = "x")] name -> module::function)`
+/// - `proc_macro2` implementation: `attr_macro!(name -> pm2 module::function)`
+/// - Fallible implementation: `attr_macro!(name -> try module::function)`
+/// - Panics reported as errors: `attr_macro!(name -> catch module::function)`
+/// - Parsed arguments and item: `attr_macro!((name: ArgsType, syn::ItemFn) -> module::function)`
+/// - Aliases, invoking the same implementation: `attr_macro!((name, alias(old, other)) -> module::function)`
+/// - Deprecated, warning where it's applied: `attr_macro!((name, deprecated("use `#[other]` instead")) -> module::function)`
 ///
 /// See: [`macros!`](crate::macros)
 #[macro_export]
 macro_rules! attr_macro {
-    // base implementation for direct function reference
-    ($name:ident -> $func:ident) => {
-        #[proc_macro_attribute]
-        pub fn $name(attr: proc_macro::TokenStream, item: proc_macro::TokenStream) -> proc_macro::TokenStream {
-            $func(attr, item)
-        }
-    };
-
-    // base implementation for using existing modules
-    ($name:ident -> use $module:ident :: $func:ident) => {
-        #[proc_macro_attribute]
-        pub fn $name(attr: proc_macro::TokenStream, item: proc_macro::TokenStream) -> proc_macro::TokenStream {
-            $module::$func(attr, item)
-        }
+    ($(#[$($meta:tt)*])* ($name:ident : $attr:ty, $item:ty $(, alias($($alias:ident),* $(,)?))? $(, deprecated($note:literal))?) -> $($impl:tt)+) => {
+        $crate::__delegate!(
+            @attrs [attribute $name [$($($alias)*)?] [$($note)?]] [[$attr, $item]] [] [] [$(#[$($meta)*])*] $($impl)+
+        );
     };
-
-    // declares module explicitly and delegates to use variant
-    ($name:ident -> mod $module:ident :: $func:ident) => {
-        mod $module;
-        $crate::attr_macro!($name -> use $module :: $func);
+    ($(#[$($meta:tt)*])* ($name:ident $(, alias($($alias:ident),* $(,)?))? $(, deprecated($note:literal))?) -> $($impl:tt)+) => {
+        $crate::__delegate!(
+            @attrs [attribute $name [$($($alias)*)?] [$($note)?]] [] [] [] [$(#[$($meta)*])*] $($impl)+
+        );
     };
-
-    // module reference (implicit mod - delegates to explicit mod)
-    ($name:ident -> $module:ident :: $func:ident) => {
-        $crate::attr_macro!($name -> mod $module :: $func);
-    };
-
-    // nested modules (use existing - base implementation)
-    ($name:ident -> use $($module:ident)::+ :: $func:ident) => {
-        #[proc_macro_attribute]
-        pub fn $name(attr: proc_macro::TokenStream, item: proc_macro::TokenStream) -> proc_macro::TokenStream {
-            $($module::)+$func(attr, item)
-        }
-    };
-
-    // nested modules (declare root module - delegates to use)
-    ($name:ident -> mod $first:ident $(:: $rest:ident)+ :: $func:ident) => {
-        mod $first;
-        $crate::attr_macro!($name -> use $first$(::$rest)+ :: $func);
-    };
-
-    // nested modules (implicit mod - delegates to explicit mod)
-    ($name:ident -> $first:ident $(:: $rest:ident)+ :: $func:ident) => {
-        $crate::attr_macro!($name -> mod $first $(:: $rest)+ :: $func);
-    };
-
-    // path variants (base implementations since they create their own internal module anyway)
-    ($name:ident -> $path:literal :: $func:ident) => {
-        #[proc_macro_attribute]
-        pub fn $name(attr: proc_macro::TokenStream, item: proc_macro::TokenStream) -> proc_macro::TokenStream {
-            #[path = $path]
-            mod __inner;
-            __inner::$func(attr, item)
-        }
-    };
-
-    // crate-relative path (prefixed with @)
-    ($name:ident -> @$path:literal :: $func:ident) => {
-        #[proc_macro_attribute]
-        pub fn $name(attr: proc_macro::TokenStream, item: proc_macro::TokenStream) -> proc_macro::TokenStream {
-            mod __inner {
-                include!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $path));
-            }
-            __inner::$func(attr, item)
-        }
+    ($(#[$($meta:tt)*])* $name:ident -> $($impl:tt)+) => {
+        $crate::__delegate!(@attrs [attribute $name [] []] [] [] [] [$(#[$($meta)*])*] $($impl)+);
     };
 }
 
@@ -172,144 +90,459 @@ macro_rules! attr_macro {
 /// - Nested modules: `derive_macro!(Name -> a::b::c::function)`
 /// - Literal path: `derive_macro!(Name -> "path/to/file.rs"::function)`
 /// - Crate-relative path: `derive_macro!(Name -> @"path/from/crate/root.rs"::function)`
+/// - Build script output (relative to `OUT_DIR`): `derive_macro!(Name -> @out/"generated.rs"::function)`
+/// - Workspace-relative path: `derive_macro!(Name -> @workspace/"shared/impl.rs"::function)`
+/// - Path relative to an environment variable: `derive_macro!(Name -> @"ENV_VAR"/"impl.rs"::function)`
+/// - Path declared at the crate root as a named module: `derive_macro!(Name -> "path/to/file.rs" as module::function)`
+/// - With doc comments and other attributes: `derive_macro!(#[doc = "..."] Name -> function)`
+/// - Behind a cfg (also gating the module declaration): `derive_macro!(#[cfg(feature = "x")] Name -> module::function)`
 /// - With attributes: `derive_macro!((Name, attributes(attr1, attr2)) -> module::function)`
+/// - `proc_macro2` implementation: `derive_macro!(Name -> pm2 module::function)`
+/// - Fallible implementation: `derive_macro!(Name -> try module::function)`
+/// - Panics reported as errors: `derive_macro!(Name -> catch module::function)`
+/// - Parsed input: `derive_macro!((Name: syn::DeriveInput) -> module::function)`
+/// - Aliases, invoking the same implementation: `derive_macro!((Name, attributes(attr), alias(Old)) -> module::function)`
+/// - Deprecated, warning where it's derived: `derive_macro!((Name, deprecated("derive `Other` instead")) -> module::function)`
+///
+/// With the `frontend` feature (enabled by default), the generated entry point is named
+/// `__derive_Name`, so `Name` stays free for the items of the crate root (such as a unit struct
+/// or function of that name). Without it (`default-features = false`), the entry point is
+/// `pub fn Name`, which clashes with any other function, constant, or unit or tuple struct named
+/// `Name` at the crate root, defined or imported (traits and other types don't clash, as they're
+/// in the type namespace).
 ///
 /// See: [`macros!`](crate::macros)
 #[macro_export]
 macro_rules! derive_macro {
+    ($(#[$($meta:tt)*])* ($name:ident : $input:ty $(, attributes($($attr:ident),* $(,)?))? $(, alias($($alias:ident),* $(,)?))? $(, deprecated($note:literal))?) -> $($impl:tt)+) => {
+        $crate::__delegate!(
+            @attrs [derive $name [$($($alias)*)?] [$($note)?] [$(, attributes($($attr),*))?]] [[$input]] [] [] [$(#[$($meta)*])*] $($impl)+
+        );
+    };
+    ($(#[$($meta:tt)*])* ($name:ident $(, attributes($($attr:ident),* $(,)?))? $(, alias($($alias:ident),* $(,)?))? $(, deprecated($note:literal))?) -> $($impl:tt)+) => {
+        $crate::__delegate!(
+            @attrs [derive $name [$($($alias)*)?] [$($note)?] [$(, attributes($($attr),*))?]] [] [] [] [$(#[$($meta)*])*] $($impl)+
+        );
+    };
+    ($(#[$($meta:tt)*])* $name:ident -> $($impl:tt)+) => {
+        $crate::__delegate!(@attrs [derive $name [] [] []] [] [] [] [$(#[$($meta)*])*] $($impl)+);
+    };
+}
+
+/// Shared implementation of [`proc_macro!`](crate::proc_macro),
+/// [`attr_macro!`](crate::attr_macro) and [`derive_macro!`](crate::derive_macro).
+///
+/// The kind of the macro is carried along as `[function name [aliases] [note] gate [attrs]]`,
+/// `[attribute name [aliases] [note] gate [attrs]]` or
+/// `[derive Name [aliases] [note] [helper attributes] gate [attrs]]`, where `note` is that of
+/// the `deprecated("note")` option (if any), `gate` is the
+/// `(all(...))` of the entry's `#[cfg]`s (applied to everything it declares)
+/// and `attrs` are the other outer attributes, forwarded to the entry point.
+/// The implementation is resolved in three steps: leading markers (`pm2`, `try`,
+/// `catch`, after the `[input types]` of typed entries) are collected first,
+/// then the source form is reduced to the items to declare at the crate root,
+/// the items to declare inside the generated function and the path to call,
+/// and finally the entry point itself is emitted. The source form is also kept
+/// as written, for diagnostics.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __delegate {
     // -------------------------------------------------
+    // attributes
     // -------------------------------------------------
-    // base implementations for various pattern types
+
+    (@attrs [$($kind:tt)*] $modes:tt [$($cfg:tt)*] $meta:tt [#[cfg($($predicate:tt)*)] $($attr:tt)*] $($impl:tt)+) => {
+        $crate::__delegate!(
+            @attrs [$($kind)*] $modes [$($cfg)* $($predicate)*,] $meta [$($attr)*] $($impl)+
+        );
+    };
+    (@attrs [$($kind:tt)*] $modes:tt $cfg:tt [$($meta:tt)*] [#[$($other:tt)*] $($attr:tt)*] $($impl:tt)+) => {
+        $crate::__delegate!(
+            @attrs [$($kind)*] $modes $cfg [$($meta)* #[$($other)*]] [$($attr)*] $($impl)+
+        );
+    };
+    (@attrs [$($kind:tt)*] $modes:tt [$($cfg:tt)*] $meta:tt [] $($impl:tt)+) => {
+        $crate::__delegate!(@modes [$($kind)* (all($($cfg)*)) $meta] $modes $($impl)+);
+    };
+
+    // -------------------------------------------------
+    // markers
     // -------------------------------------------------
 
-    // direct function reference (base implementation for general attributes)
-    (($name:ident $(, $attr:tt)*) -> use $func:ident) => {
-        #[allow(non_snake_case)]
-        #[proc_macro_derive($name $(, $attr)*)]
-        pub fn $name(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-            $func(input)
-        }
+    // a path that merely starts with a marker-like ident (e.g. `pm2::function`)
+    (@modes $kind:tt [$($mode:tt)*] $first:ident :: $($impl:tt)+) => {
+        $crate::__delegate!(@source $kind [$($mode)*] [$first :: $($impl)+] $first :: $($impl)+);
+    };
+    (@modes $kind:tt [$($mode:tt)*] pm2 $($impl:tt)+) => {
+        $crate::__delegate!(@modes $kind [$($mode)* pm2] $($impl)+);
+    };
+    (@modes $kind:tt [$($mode:tt)*] try $($impl:tt)+) => {
+        $crate::__delegate!(@modes $kind [$($mode)* try] $($impl)+);
+    };
+    (@modes $kind:tt [$($mode:tt)*] catch $($impl:tt)+) => {
+        $crate::__delegate!(@modes $kind [$($mode)* catch] $($impl)+);
+    };
+    // the implementation as `macros!` was given it, before rewriting it
+    (@modes $kind:tt $modes:tt [$($origin:tt)+] $($impl:tt)+) => {
+        $crate::__delegate!(@source $kind $modes [$($origin)+] $($impl)+);
+    };
+    (@modes $kind:tt [$($mode:tt)*] $($impl:tt)+) => {
+        $crate::__delegate!(@source $kind [$($mode)*] [$($impl)+] $($impl)+);
     };
 
-    // direct function reference (base implementation for helper attributes)
-    (($name:ident, attributes($($attr:ident),*)) -> use $func:ident) => {
-        #[allow(non_snake_case)]
-        #[proc_macro_derive($name, attributes($($attr),*))]
-        pub fn $name(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-            $func(input)
-        }
+    // -------------------------------------------------
+    // source forms
+    // -------------------------------------------------
+
+    // module path without declaration (already imported)
+    (@source $kind:tt $modes:tt $origin:tt use $($path:ident)::+) => {
+        $crate::__delegate!(@emit $kind $modes $origin [] [] [$($path)::+]);
     };
 
-    // module reference (use existing - base implementation for general attributes)
-    (($name:ident $(, $attr:tt)*) -> use $module:ident :: $func:ident) => {
-        #[allow(non_snake_case)]
-        #[proc_macro_derive($name $(, $attr)*)]
-        pub fn $name(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-            $module::$func(input)
+    // declares the root module explicitly
+    (@source $kind:tt $modes:tt $origin:tt mod $first:ident $(:: $rest:ident)+) => {
+        $crate::__delegate!(
+            @emit $kind $modes $origin [{mod $first;}] [] [$first $(:: $rest)+]
+        );
+    };
+
+    // direct function reference (already in scope)
+    (@source $kind:tt $modes:tt $origin:tt $func:ident) => {
+        $crate::__delegate!(@emit $kind $modes $origin [] [] [$func]);
+    };
+
+    // implicit module (defaults to explicit module declaration)
+    (@source $kind:tt $modes:tt $origin:tt $first:ident $(:: $rest:ident)+) => {
+        $crate::__delegate!(@source $kind $modes $origin mod $first $(:: $rest)+);
+    };
+
+    // literal file path (module is declared inside the generated function, once per entry
+    // using the file)
+    (@source $kind:tt $modes:tt $origin:tt $path:literal :: $func:ident) => {
+        $crate::__delegate!(
+            @emit $kind $modes $origin []
+            [#[allow(clippy::duplicate_mod)] #[path = $path] mod __inner;]
+            [__inner::$func]
+        );
+    };
+
+    // literal file path, declared at the crate root under the given name
+    (@source $kind:tt $modes:tt $origin:tt $path:literal as $module:ident :: $func:ident) => {
+        $crate::__delegate!(
+            @emit $kind $modes $origin
+            [{$crate::__delegate!(@module $module $path);}] [] [$module::$func]
+        );
+    };
+
+    // crate-relative path, declared at the crate root under the given name
+    (@source $kind:tt $modes:tt $origin:tt @$path:literal as $module:ident :: $func:ident) => {
+        $crate::__delegate!(
+            @emit $kind $modes $origin
+            [{$crate::__delegate!(@module $module @$path);}] [] [$module::$func]
+        );
+    };
+
+    // crate-relative path (prefixed with @), declared inside the generated function
+    (@source $kind:tt $modes:tt $origin:tt @$path:literal :: $func:ident) => {
+        $crate::__delegate!(
+            @emit $kind $modes $origin []
+            [$crate::__delegate!(@module __inner @$path);]
+            [__inner::$func]
+        );
+    };
+
+    // path anchored elsewhere (`@out/`, `@workspace/` or `@"ENV_VAR"/`), declared inside the
+    // generated function
+    (@source $kind:tt $modes:tt $origin:tt @ $anchor:tt / $path:literal :: $func:ident) => {
+        $crate::__delegate!(
+            @emit $kind $modes $origin []
+            [$crate::__delegate!(@module __inner @ $anchor / $path);]
+            [__inner::$func]
+        );
+    };
+
+    // anchored path, declared at the crate root under the given name
+    (@source $kind:tt $modes:tt $origin:tt @ $anchor:tt / $path:literal as $module:ident :: $func:ident) => {
+        $crate::__delegate!(
+            @emit $kind $modes $origin
+            [{$crate::__delegate!(@module $module @ $anchor / $path);}] [] [$module::$func]
+        );
+    };
+
+    // -------------------------------------------------
+    // modules
+    // -------------------------------------------------
+
+    (@module $module:ident $path:literal) => {
+        #[path = $path]
+        mod $module;
+    };
+    // `#[path]` only takes a literal, relative to the file it's in, so the path relative to
+    // the manifest directory is made absolute by the frontend (or included without it)
+    (@module $module:ident @$path:literal) => {
+        $crate::__crate_file!($module $path);
+    };
+    // files outside of the crate can only be included (a missing file is reported by
+    // `include!`, along with the full path it was expected at)
+    (@module $module:ident @out / $path:literal) => {
+        $crate::__delegate!(
+            @include $module
+            [env!(
+                "OUT_DIR",
+                "`@out/` implementation paths need a build script, but `OUT_DIR` isn't set"
+            )]
+            $path
+        );
+    };
+    // cargo doesn't tell the workspace root, so it's found by the frontend (or expected to be
+    // configured in `.cargo/config.toml` without it)
+    (@module $module:ident @workspace / $path:literal) => {
+        $crate::__delegate!(@include $module [$crate::__workspace_dir!()] $path);
+    };
+    (@module $module:ident @ $var:literal / $path:literal) => {
+        $crate::__delegate!(@include $module [env!($var)] $path);
+    };
+    (@module $module:ident @glob / $path:literal) => {
+        ::core::compile_error!(concat!(
+            "`@glob/",
+            stringify!($path),
+            "` registers a macro per matching file, so it can only be used in `macros!`, \
+             without `as`"
+        ));
+    };
+    (@module $module:ident @ $anchor:ident / $path:literal) => {
+        ::core::compile_error!(concat!(
+            "unknown implementation path anchor `@",
+            stringify!($anchor),
+            "/`, expected `@out/`, `@workspace/`, `@glob/` or `@\"ENV_VAR\"/`"
+        ));
+    };
+
+    (@include $module:ident [$($dir:tt)*] $path:literal) => {
+        mod $module {
+            include!(concat!($($dir)*, "/", $path));
         }
     };
 
-    // module reference (use existing - base implementation for helper attributes)
-    (($name:ident, attributes($($attr:ident),*)) -> use $module:ident :: $func:ident) => {
-        #[allow(non_snake_case)]
-        #[proc_macro_derive($name, attributes($($attr),*))]
+    // -------------------------------------------------
+    // entry points
+    // -------------------------------------------------
+
+    (@emit [function $name:ident $aliases:tt $deprecated:tt $gate:tt [$($meta:tt)*]] $modes:tt [$($origin:tt)*] [$({$($item:tt)*})*] [$($local:tt)*] $path:tt) => {
+        $(#[cfg $gate] $($item)*)*
+
+        #[cfg $gate]
+        $($meta)*
+        #[proc_macro]
         pub fn $name(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-            $module::$func(input)
+            $($local)*
+            $crate::__delegate!(
+                @deprecate $deprecated function [stringify!($name)]
+                $crate::__delegate!(
+                    @call function [concat!(stringify!($name), "!"), $($origin)*] $modes $path (input)
+                )
+            )
         }
+
+        $crate::__delegate!(
+            @aliases [function $deprecated $gate [$($meta)*]] $modes [$($origin)*] [$($local)*] $path $aliases
+        );
     };
+    (@emit [attribute $name:ident $aliases:tt $deprecated:tt $gate:tt [$($meta:tt)*]] $modes:tt [$($origin:tt)*] [$({$($item:tt)*})*] [$($local:tt)*] $path:tt) => {
+        $(#[cfg $gate] $($item)*)*
 
-    // nested modules (use existing - base implementation for general attributes)
-    (($name:ident $(, $attr:tt)*) -> use $($module:ident)::+ :: $func:ident) => {
-        #[allow(non_snake_case)]
-        #[proc_macro_derive($name $(, $attr)*)]
-        pub fn $name(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-            $($module::)+$func(input)
+        #[cfg $gate]
+        $($meta)*
+        #[proc_macro_attribute]
+        pub fn $name(
+            attr: proc_macro::TokenStream,
+            item: proc_macro::TokenStream,
+        ) -> proc_macro::TokenStream {
+            $($local)*
+            $crate::__delegate!(
+                @deprecate $deprecated attribute [stringify!($name)]
+                $crate::__delegate!(
+                    @call attribute [concat!("#[", stringify!($name), "]"), $($origin)*] $modes $path (attr, item)
+                )
+            )
         }
+
+        $crate::__delegate!(
+            @aliases [attribute $deprecated $gate [$($meta)*]] $modes [$($origin)*] [$($local)*] $path $aliases
+        );
     };
+    (@emit [derive $name:ident $aliases:tt $deprecated:tt [$($attr:tt)*] $gate:tt [$($meta:tt)*]] $modes:tt [$($origin:tt)*] [$({$($item:tt)*})*] [$($local:tt)*] $path:tt) => {
+        $(#[cfg $gate] $($item)*)*
 
-    // nested modules (use existing - base implementation for helper attributes)
-    (($name:ident, attributes($($attr:ident),*)) -> use $($module:ident)::+ :: $func:ident) => {
-        #[allow(non_snake_case)]
-        #[proc_macro_derive($name, attributes($($attr),*))]
-        pub fn $name(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-            $($module::)+$func(input)
+        // the derive is exported under the name given to `proc_macro_derive`, so the function
+        // itself is renamed out of the way of the items of the crate root when possible
+        $crate::__entry_point! {
+            [__derive_]
+            #[cfg $gate]
+            $($meta)*
+            #[allow(non_snake_case)]
+            #[proc_macro_derive($name $($attr)*)]
+            pub fn $name(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+                $($local)*
+                $crate::__delegate!(
+                    @deprecate $deprecated derive [stringify!($name), &input]
+                    $crate::__delegate!(
+                        @call derive [concat!("#[derive(", stringify!($name), ")]"), $($origin)*] $modes $path (input)
+                    )
+                )
+            }
         }
+
+        $crate::__delegate!(
+            @aliases [derive $deprecated [$($attr)*] $gate [$($meta)*]] $modes [$($origin)*] [$($local)*] $path $aliases
+        );
+    };
+
+    // deprecated entry points add a reference to a `#[deprecated]` item to their output
+    (@deprecate [] $kind:ident $args:tt $output:expr) => {
+        $output
+    };
+    (@deprecate [$note:literal] $kind:ident [$($arg:expr),+] $output:expr) => {
+        $crate::__private::Deprecation::$kind($note, $($arg),+).apply($output)
+    };
+
+    // every alias gets an entry point of its own, calling the same implementation. the items
+    // declared at the crate root are only declared along with the first one
+    (@aliases $kind:tt $modes:tt $origin:tt $local:tt $path:tt []) => {};
+    (@aliases [$kind:ident $($shape:tt)*] $modes:tt $origin:tt $local:tt $path:tt [$alias:ident $($rest:ident)*]) => {
+        $crate::__delegate!(@emit [$kind $alias [] $($shape)*] $modes $origin [] $local $path);
+        $crate::__delegate!(@aliases [$kind $($shape)*] $modes $origin $local $path [$($rest)*]);
     };
 
     // -------------------------------------------------
-    // delegating variants
+    // signatures
     // -------------------------------------------------
 
-    // direct function reference (delegates to use variant)
-    (($name:ident $(, $attr:tt)*) -> $func:ident) => {
-        $crate::derive_macro!(($name $(, $attr)*) -> use $func);
-    };
-    (($name:ident, attributes($($attr:tt),*)) -> $func:ident) => {
-        $crate::derive_macro!(($name, attributes($($attr),*)) -> use $func);
-    };
+    // the implementation is called through a check of its signature against the function
+    // pointer type the slots make up, so that a mismatch is reported as such (naming the
+    // expected signature) and the call still has the type the entry point expects, rather than
+    // raising type errors of its own. it's imported first, so that the errors point at its
+    // name, which the frontend moves onto the entry (they point at the whole invocation
+    // otherwise)
+    (@check [$kind:ident $input:tt $output:tt] [$name:expr, $($origin:tt)*] [$func:ident] $arguments:tt) => {
+        $crate::__spanned!([$($origin)*] $crate::__private::signature::Expected::<
+            $crate::__delegate!(@pointer $kind $input $output)
+        >::$kind($func, $arguments))
+    };
+    (@check $check:tt $context:tt [$first:ident :: $($rest:tt)+] $arguments:tt) => {{
+        $crate::__delegate!(@import $check $context $arguments [$first] $($rest)+)
+    }};
+    (@import $check:tt $context:tt $arguments:tt [$($module:ident)::+] $next:ident :: $($rest:tt)+) => {
+        $crate::__delegate!(@import $check $context $arguments [$($module)::+ :: $next] $($rest)+)
+    };
+    (@import $check:tt $context:tt $arguments:tt [$($module:ident)::+] $func:ident) => {{
+        use $($module)::+ :: $func;
+        $crate::__delegate!(@check $check $context [$func] $arguments)
+    }};
 
-    // module reference (declare module explicitly - delegates to use)
-    (($name:ident $(, $attr:tt)*) -> mod $module:ident :: $func:ident) => {
-        mod $module;
-        $crate::derive_macro!(($name $(, $attr)*) -> use $module :: $func);
+    (@pointer attribute _ $output:tt) => {
+        fn(proc_macro::TokenStream, proc_macro::TokenStream) -> $crate::__delegate!(@output _ $output)
     };
-    (($name:ident, attributes($($attr:tt),*)) -> mod $module:ident :: $func:ident) => {
-        mod $module;
-        $crate::derive_macro!(($name, attributes($($attr),*))-> use $module :: $func);
+    (@pointer attribute pm2 $output:tt) => {
+        fn($crate::__pm2_token_stream!(), $crate::__pm2_token_stream!())
+            -> $crate::__delegate!(@output pm2 $output)
     };
-
-    // module reference (implicit mod - delegates to explicit mod)
-    (($name:ident $(, $attr:tt)*) -> $module:ident :: $func:ident) => {
-        $crate::derive_macro!(($name $(, $attr)*) -> mod $module :: $func);
+    (@pointer attribute [$args:ty, $item:ty] $output:tt) => {
+        fn($args, $item) -> $crate::__delegate!(@output [] $output)
     };
-    (($name:ident, attributes($($attr:tt),*)) -> $module:ident :: $func:ident) => {
-        $crate::derive_macro!(($name, attributes($($attr),*))-> mod $module :: $func);
+    (@pointer $kind:ident _ $output:tt) => {
+        fn(proc_macro::TokenStream) -> $crate::__delegate!(@output _ $output)
     };
-
-    // nested modules (declare root module - delegates to use)
-    (($name:ident $(, $attr:tt)*) -> mod $first:ident $(:: $rest:ident)+ :: $func:ident) => {
-        mod $first;
-        $crate::derive_macro!(($name $(, $attr)*) -> use $first$(::$rest)+ :: $func);
+    (@pointer $kind:ident pm2 $output:tt) => {
+        fn($crate::__pm2_token_stream!()) -> $crate::__delegate!(@output pm2 $output)
     };
-    (($name:ident, attributes($($attr:tt),*)) -> mod $first:ident $(:: $rest:ident)+ :: $func:ident) => {
-        mod $first;
-        $crate::derive_macro!(($name, attributes($($attr),*)) -> use $first$(::$rest)+ :: $func);
+    (@pointer $kind:ident [$input:ty] $output:tt) => {
+        fn($input) -> $crate::__delegate!(@output [] $output)
     };
 
-    // nested modules (implicit mod - delegates to explicit mod)
-    (($name:ident $(, $attr:tt)*) -> $first:ident $(:: $rest:ident)+ :: $func:ident) => {
-        $crate::derive_macro!(($name $(, $attr)*) -> mod $first $(:: $rest)+ :: $func);
+    // implementations taking `proc_macro2` or parsed inputs return `proc_macro2` tokens
+    (@output _ _) => {
+        proc_macro::TokenStream
+    };
+    (@output $input:tt _) => {
+        $crate::__pm2_token_stream!()
     };
-    (($name:ident, attributes($($attr:tt),*)) -> $first:ident $(:: $rest:ident)+ :: $func:ident) => {
-        $crate::derive_macro!(($name, attributes($($attr),*)) -> mod $first $(:: $rest)+ :: $func);
+    (@output $input:tt try) => {
+        ::core::result::Result<$crate::__delegate!(@output $input _), _>
     };
 
     // -------------------------------------------------
-    // path variants (base implementations since they create their own module anyways)
+    // calls
     // -------------------------------------------------
 
-    // path variants (base implementations since they create their own module anyway)
-    (($name:ident $(, $attr:tt)*) -> $path:literal :: $func:ident) => {
-        #[allow(non_snake_case)]
-        #[proc_macro_derive($name $(, $attr)*)]
-        pub fn $name(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-            #[path = $path]
-            mod __inner;
-            __inner::$func(input)
-        }
+    // markers are sorted into `[arguments result guard]` slots first, `_` meaning the default
+    // and `[types]` parsed arguments
+    (@call $kind:ident $context:tt $modes:tt $path:tt $args:tt) => {
+        $crate::__delegate!(@call $kind $context [_ _ _] $modes $path $args)
+    };
+    (@call $kind:ident $context:tt [$input:tt $output:tt $guard:tt] [[$($ty:ty),+] $($mode:tt)*] $path:tt $args:tt) => {
+        $crate::__delegate!(@call $kind $context [[$($ty),+] $output $guard] [$($mode)*] $path $args)
+    };
+    (@call $kind:ident $context:tt [$input:tt $output:tt $guard:tt] [pm2 $($mode:tt)*] $path:tt $args:tt) => {
+        $crate::__delegate!(@call $kind $context [pm2 $output $guard] [$($mode)*] $path $args)
+    };
+    (@call $kind:ident $context:tt [$input:tt $output:tt $guard:tt] [try $($mode:tt)*] $path:tt $args:tt) => {
+        $crate::__delegate!(@call $kind $context [$input try $guard] [$($mode)*] $path $args)
+    };
+    (@call $kind:ident $context:tt [$input:tt $output:tt $guard:tt] [catch $($mode:tt)*] $path:tt $args:tt) => {
+        $crate::__delegate!(@call $kind $context [$input $output catch] [$($mode)*] $path $args)
+    };
+    // the arguments and result make up the signature the implementation is checked against
+    (@call $kind:ident $context:tt [$input:tt $output:tt $guard:tt] [] $path:tt $args:tt) => {
+        $crate::__delegate!(@invoke [$kind $input $output] $context [$input $output $guard] $path $args)
     };
 
-    // crate-relative path (prefixed with @)
-    (($name:ident $(, $attr:tt)*) -> @$path:literal :: $func:ident) => {
-        #[allow(non_snake_case)]
-        #[proc_macro_derive($name $(, $attr)*)]
-        pub fn $name(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-            mod __inner {
-                include!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $path));
-            }
-            __inner::$func(input)
+    (@invoke $check:tt $context:tt [_ _ _] $path:tt ($($arg:ident),*)) => {
+        $crate::__delegate!(@check $check $context $path ($($arg,)*))
+    };
+    (@invoke $check:tt $context:tt [pm2 _ _] $path:tt $args:tt) => {
+        ::core::convert::Into::into(
+            $crate::__delegate!(@check $check $context $path ($crate::__pm2_arguments!($args)))
+        )
+    };
+    (@invoke $check:tt $context:tt [$types:tt _ _] $path:tt $args:tt) => {
+        ::core::convert::Into::into(
+            $crate::__delegate!(@check $check $context $path ($crate::__syn_arguments!($types $args)))
+        )
+    };
+    (@invoke $check:tt $context:tt [$input:tt try _] $path:tt $args:tt) => {
+        match $crate::__delegate!(@invoke $check $context [$input _ _] $path $args) {
+            ::core::result::Result::Ok(tokens) => ::core::convert::Into::into(tokens),
+            ::core::result::Result::Err(err) => $crate::IntoCompileError::into_compile_error(err),
         }
     };
+    (@invoke $check:tt [$name:expr, $($origin:tt)*] [$input:tt $output:tt catch] $path:tt $args:tt) => {
+        $crate::__private::catch_panic($name, stringify!($($origin)*), || {
+            $crate::__delegate!(@invoke $check [$name, $($origin)*] [$input $output _] $path $args)
+        })
+    };
+}
+
+/// The arguments of a `proc_macro2` implementation, converted.
+#[cfg(feature = "proc-macro2")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pm2_arguments {
+    (($($arg:ident),*)) => {
+        ($($crate::__private::proc_macro2::TokenStream::from($arg),)*)
+    };
+}
+
+/// The arguments of a `proc_macro2` implementation, converted.
+#[cfg(not(feature = "proc-macro2"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pm2_arguments {
+    ($args:tt) => {
+        compile_error!(
+            "the `pm2` marker requires the `proc-macro2` feature of `include_proc_macro`"
+        )
+    };
 }
 
 /// Delegates procedural macro declarations to implementation modules.
@@ -323,25 +556,144 @@ macro_rules! derive_macro {
 /// ## Supported syntax:
 /// - `function(macro_name) -> impl`: Function-like proc macros with custom name
 /// - `function -> impl`: Function-like proc macros using function name as macro name
+/// - `function(macro_name: InputType) -> impl`: Function-like proc macros whose implementation
+///   takes the parsed input (any `syn::parse::Parse` type) and returns a
+///   `proc_macro2::TokenStream`. Parse failures are reported as spanned errors. Requires the
+///   `syn` feature.
 /// - `attribute(attr_name) -> impl`: Attribute proc macros with custom name
 /// - `attribute -> impl`: Attribute proc macros using function name as macro name
+/// - `attribute(attr_name: ArgsType, syn::ItemFn) -> impl`: Attribute proc macros whose
+///   implementation takes the parsed arguments and item (any `syn::parse::Parse` types, e.g.
+///   `syn::parse::Nothing` for no arguments) and returns a `proc_macro2::TokenStream`. Parse
+///   failures are reported as spanned errors. Requires the `syn` feature.
 /// - `derive(DeriveName) -> impl`: Derive proc macros
 /// - `derive(DeriveName, attributes(attr1, attr2)) -> impl`: Derive macros with helper attributes
+/// - `derive(DeriveName: syn::DeriveInput) -> impl`: Derive macros whose implementation takes
+///   the parsed input (any `syn::parse::Parse` type) and returns a `proc_macro2::TokenStream`.
+///   Parse failures are reported as spanned errors. Requires the `syn` feature.
+/// - `function(macro_name, alias(old_name, other)) -> impl`: Macros also exported under other
+///   names, e.g. to keep a renamed macro working. Every alias gets an entry point of its own,
+///   invoking the same implementation (whose module is still declared once). `alias(..)` comes
+///   last in the spec of any kind, e.g. `attribute(attr_name: ArgsType, syn::ItemFn, alias(old))`
+///   or `derive(DeriveName, attributes(attr1), alias(OldName))`
+/// - `function(macro_name, deprecated("use `other!` instead")) -> impl`: Deprecated macros
+///   (and aliases), which warn wherever they're used with the given note. Proc macros can't emit
+///   warnings, so the output references a hidden `#[deprecated]` macro instead: the reference
+///   precedes items and statements, goes into the body of the first function (which could be
+///   a method), or is wrapped in a block along with an expression. Output that could be an
+///   associated item other than a function (constants and types), or starts with a macro call
+///   that could expand to items (e.g. `thread_local! { .. }`), is left unchanged, so it doesn't
+///   warn. Neither can macros expanding to types or patterns be deprecated this way. The
+///   warnings of a derive point at the name of the deriving item. Like `alias(..)`, it comes
+///   last in the spec of any kind, in either order with `alias(..)`
+///
+/// Function-like and attribute macros are exported under the name of their entry point, a
+/// function generated at the crate root, so no other function of the crate root can share
+/// their name. Derives are exported under the name given to them, and their entry point is
+/// named `__derive_Name` with the `frontend` feature, leaving the crate root free for a type or
+/// function named `Name`. Without it, the entry point is `pub fn Name`, so the crate root can't
+/// define or import a function, constant, or unit or tuple struct named `Name`.
+///
+/// Each entry can be preceded by doc comments and other outer attributes (e.g. `#[deprecated]`
+/// or `#[cfg_attr(...)]`), which are forwarded to the generated entry point. `#[cfg(...)]`
+/// attributes gate the whole entry, including the module it declares.
+///
+/// Entries sharing a module can be grouped, declaring the module once:
+/// - `mod module { entries }` or `module { entries }`: declares `module` and resolves the
+///   implementation paths of the entries inside against it
+/// - `use module { entries }`: the same, for an already available module
 ///
-/// Where `impl` can be:
+/// Groups can be nested (`outer { inner { entries } }`, relative to the enclosing group) and
+/// gated with `#[cfg(...)]`, which then applies to the module and every entry in the group.
+///
+/// Where `impl` can be any of the following, for every kind, whether the macro is named after
+/// the implementation or explicitly (with helper attributes or not, for derives):
 /// - Direct function: `function`
-/// - Module reference with implicit module declaration (default): `module::function`
+/// - Module reference with implicit module declaration (default): `module::function`. A
+///   module referred to by several entries is only declared once, so they can share it
+///   without `use` (under the cfgs of all of them, or of the first one without the frontend)
 /// - Module reference with explicit declaration: `mod module::function`
 /// - Module reference without declaration (already imported): `use module::function`
 /// - Nested modules: `a::b::c::function`
 /// - Literal path: `"path/to/file.rs"::function`
-/// - Crate-relative path: `@"path/from/crate/root.rs"::function`
+/// - Crate-relative path: `@"path/from/crate/root.rs"::function`, relative to the manifest
+///   directory. With the `frontend` feature, the file is declared as a module of its own (at its
+///   absolute path), so its submodules (relative to the file), inner attributes and `super::`
+///   paths (to the crate root) work as usual. Without it, the file is included like the anchored
+///   paths below
+/// - All the public functions of a module: `module::*` (or `mod module::*`, `use module::*`),
+///   registering one macro per `pub fn` (in order), named after the function, all with the same
+///   kind, markers and attributes. `module::prefix_*` only registers the functions starting with
+///   `prefix_`. The module is looked up in `src/` (as `module.rs` or `module/mod.rs`), and
+///   `#[cfg(...)]`s on the functions are applied to their entries. Requires the `frontend` feature
+/// - Glob of crate-relative paths: `@glob/"src/macros/*.rs"::function`, registering one macro
+///   per matching file (in order), named after the file stem, all with the same kind, markers
+///   and attributes. `*` matches any part of a file or directory name (it isn't expanded in the
+///   other path forms). Requires the `frontend` feature. Note that cargo doesn't know about the
+///   directories, so adding a file may need a rebuild of the crate to be picked up
+/// - Build script output: `@out/"path/from/out_dir.rs"::function`. The file is included from
+///   `OUT_DIR`, so it should be self-contained. Missing files (or a missing build script) are
+///   reported at compile time, along with the path the file was expected at
+/// - Workspace-relative path: `@workspace/"path/from/workspace/root.rs"::function`, included
+///   like the above. Cargo doesn't expose the workspace root, so the frontend looks for the
+///   closest manifest with a `[workspace]` table, unless `CARGO_WORKSPACE_DIR` is set. Without
+///   the frontend, that environment variable is needed, and can be set up in the workspace's
+///   `.cargo/config.toml` with `[env] CARGO_WORKSPACE_DIR = { value = "", relative = true }`
+/// - Path relative to an environment variable: `@"ENV_VAR"/"path/from/var.rs"::function`,
+///   included like the above
+/// - Any of the path forms declared at the crate root as a named module, which the rest of
+///   the crate (and other entries referring to the same module) can use:
+///   `"path/to/file.rs" as module::function`, `@"path/from/crate/root.rs" as module::function`
+///
+/// Any of the above can be prefixed with a marker:
+/// - `pm2`: the implementation takes and returns `proc_macro2::TokenStream`s, which
+///   makes it usable (and unit-testable) outside of a proc macro context. Requires
+///   the `proc-macro2` feature.
+/// - `try`: the implementation returns `Result<TokenStream, E>`, and an `Err` is
+///   expanded into a `compile_error!` through [`IntoCompileError`]. Combines with
+///   `pm2` (e.g. `try pm2 module::function` for a `syn::Result<proc_macro2::TokenStream>`).
+/// - `catch`: the implementation runs under [`std::panic::catch_unwind`], and a panic
+///   (e.g. from an `unwrap()`) is reported as a `compile_error!` naming the macro and
+///   the implementation instead of an opaque "proc macro panicked". Combines with the
+///   other markers.
+///
+/// Malformed entries (an unknown kind or marker, a missing `->`, an unsupported implementation
+/// path, ...) are reported as a `compile_error!` naming the entry and listing the accepted forms.
+///
+/// Implementations whose signature doesn't fit their entry (e.g. a `fn(TokenStream)` registered
+/// as an attribute, or one returning a `String` without `try`) are reported with the signature
+/// expected given the kind and markers, like ``attribute implementations must be
+/// `fn(TokenStream, TokenStream) -> TokenStream` ``, rather than as type mismatches inside the
+/// generated entry point (the implementation is called through the check, so it's the only error).
+///
+/// With the `frontend` feature (enabled by default), a companion proc macro parses the whole
+/// invocation before any of it is expanded, and reports all at once:
+/// - malformed entries, pointing at the offending tokens
+/// - modules, files and functions that can't be found, along with the closest existing name
+/// - macros named like an earlier one that is compiled whenever they are, or the other way
+///   around. Entries whose cfgs may exclude each other don't clash
+/// - function-like and attribute macros named like a function the crate root defines or imports
+/// - implementations whose signature, as far as their file tells, doesn't fit their entry
+///
+/// The signature mismatches only the entry points find then point at the implementation of
+/// the entry, rather than at the whole invocation. The frontend also declares every module root
+/// once, gated by the cfgs of all the entries declaring it, and expands every entry (or group)
+/// on its own, so that invocations of hundreds of entries compile in linear time. Without it
+/// (`default-features = false`), `macros!` only recurses once every few entries, so a few
+/// hundred of them still fit in the default `#![recursion_limit]`.
 ///
 /// ## Examples
-/// ```rust
+/// ```rust,ignore
 /// include_proc_macro::macros!(
+///     /// Expands to the foo of the input.
+///     ///
+///     /// Doc comments (and other attributes) end up on the generated entry point.
 ///     function(foo) -> foo_mod::implement,
 ///     function -> bar_mod::bar, // uses `bar` as the macro name
+///
+///     // only compiled (along with the `extra_mod` module) with the `extra` feature
+///     #[cfg(feature = "extra")]
+///     function(extra) -> extra_mod::implement,
 ///     
 ///     // Using an already imported module with the `use` keyword
 ///     function(baz) -> use existing_mod::function_impl,
@@ -350,290 +702,1280 @@ macro_rules! derive_macro {
 ///     function(fizz) -> mod explicit_mod::function_impl,
 ///     
 ///     function(buzz) -> "src/impls/hello.rs"::hello,
+///     // implementation takes the input parsed as `GreetArgs`
+///     function(greet: hello::GreetArgs) -> hello::greet,
 ///     // impl in custom crate path, uses 'world' as the macro name
 ///     function -> @"custom_src/impls/world.rs"::world,  
+///     // one macro per public function of `derives` starting with `derive_` (`frontend` feature)
+///     derive -> derives::derive_*,
+///     // one macro per file, e.g. `src/macros/fn/upper.rs` registers `upper!` (`frontend` feature)
+///     function -> @glob/"src/macros/fn/*.rs"::expand,
+///     // impl generated by the build script, relative to `OUT_DIR`
+///     function -> @out/"generated/tables.rs"::lookup,
+///     // impls shared across the workspace, or found through an environment variable
+///     function -> @workspace/"shared/impls.rs"::shared,
+///     function -> @"MY_MACROS_DIR"/"impls.rs"::external,
+///     // file declared once at the crate root as `world_impl`, shared by both entries
+///     function(hello) -> "src/impls/world.rs" as world_impl::hello,
+///     function(goodbye) -> "src/impls/world.rs" as world_impl::goodbye,
 ///     
 ///     attribute(my_attr) -> attrs::process,
 ///     attribute(use_attr) -> use imported_attr_mod::process,
 ///     attribute -> attrs::custom,  // uses 'custom' as the macro name
+///     // implementation takes the parsed arguments and item
+///     attribute(traced: syn::LitStr, syn::ItemFn) -> attrs::traced,
 ///     
 ///     derive(MyDerive) -> derives::generate,
 ///     derive(ImportedDerive) -> use imported_derive_mod::generate,
 ///     derive(NodeTypeChecks, attributes(node_category))
-///         -> derive_impl_with_attrs::impl_with_attributes // derive with helper attributes
+///         -> derive_impl_with_attrs::impl_with_attributes, // derive with helper attributes
+///     // also exported as `OldDerive`, for the crates still using the previous name
+///     derive(NewDerive, alias(OldDerive)) -> derives::generate_new,
+///
+///     // implementation works with `proc_macro2::TokenStream`s
+///     function(testable) -> pm2 testable_mod::expand,
+///     // warns "use of deprecated macro `legacy`: use `testable!` instead" where it's used
+///     function(legacy, deprecated("use `testable!` instead")) -> pm2 testable_mod::expand,
+///     // implementation returns a `Result`, errors become `compile_error!`s
+///     derive(Checked) -> try pm2 derives::checked,
+///     // panics in the implementation are reported as `compile_error!`s
+///     derive(Careful) -> catch derives::careful,
+///     // implementation takes a `syn::DeriveInput` and returns `syn::Result<proc_macro2::TokenStream>`
+///     derive(Parsed: syn::DeriveInput, attributes(parsed)) -> try derives::parsed,
+///
+///     // declares `shapes` once, `circle` and `square` resolve to `shapes::circle` and `shapes::square`
+///     mod shapes {
+///         function -> circle,
+///         derive(Square) -> square,
+///     }
 /// );
 /// ```
 #[macro_export]
 macro_rules! macros {
     () => {};
 
-    // function patterns with trailing comma
-    (function -> $module:ident :: $func:ident , $($tail:tt)*) => {
-        $crate::proc_macro!($func -> $module::$func);
-        $crate::macros!($($tail)*);
+    // -------------------------------------------------
+    // groups
+    // -------------------------------------------------
+
+    // the state is `[seen module roots] [cfg attributes] [group prefix] [enclosing groups]`,
+    // where the seen roots start with `frontend` if the frontend has declared them already
+    (@scope $seen:tt $cfgs:tt $prefix:tt []) => {};
+    (@scope $seen:tt $cfgs:tt $prefix:tt [[$outer:tt $outer_prefix:tt [$($tail:tt)*]] $($stack:tt)*]) => {
+        $crate::macros!(@scope $seen $outer $outer_prefix [$($stack)*] $($tail)*);
     };
-    (function -> $func:ident , $($tail:tt)*) => {
-        $crate::proc_macro!($func -> $func);
-        $crate::macros!($($tail)*);
+
+    // a group declaring its module (explicitly or implicitly), only at the top level
+    (@scope [$($seen:tt)*] [$($outer:tt)*] [] [$($stack:tt)*] $(#[cfg($($cfg:tt)*)])* mod $first:ident $(:: $rest:ident)* { $($inner:tt)* } $(, $($tail:tt)*)?) => {
+        $crate::macros!(@declare $first {mod $first;} [$($outer)* $(#[cfg($($cfg)*)])*] [$($seen)*] $);
+        $crate::macros!(
+            @scope [$($seen)* {$first}]
+            [$($outer)* $(#[cfg($($cfg)*)])*] [$first $(:: $rest)*]
+            [[[$($outer)*] [] [$($($tail)*)?]] $($stack)*]
+            $($inner)*
+        );
+    };
+    // a group referring to an already available module, or nested in another group
+    (@scope $seen:tt [$($outer:tt)*] [$($prefix:ident)::*] [$($stack:tt)*] $(#[cfg($($cfg:tt)*)])* use $($module:ident)::+ { $($inner:tt)* } $(, $($tail:tt)*)?) => {
+        $crate::macros!(
+            @scope $seen
+            [$($outer)* $(#[cfg($($cfg)*)])*] [$($prefix ::)* $($module)::+]
+            [[[$($outer)*] [$($prefix)::*] [$($($tail)*)?]] $($stack)*]
+            $($inner)*
+        );
+    };
+    (@scope $seen:tt $cfgs:tt [] $stack:tt $(#[cfg($($cfg:tt)*)])* $first:ident $(:: $rest:ident)* { $($inner:tt)+ } $($tail:tt)*) => {
+        $crate::macros!(@scope $seen $cfgs [] $stack $(#[cfg($($cfg)*)])* mod $first $(:: $rest)* { $($inner)+ } $($tail)*);
+    };
+    (@scope $seen:tt $cfgs:tt $prefix:tt $stack:tt $(#[cfg($($cfg:tt)*)])* $first:ident $(:: $rest:ident)* { $($inner:tt)+ } $($tail:tt)*) => {
+        $crate::macros!(@scope $seen $cfgs $prefix $stack $(#[cfg($($cfg)*)])* use $first $(:: $rest)* { $($inner)+ } $($tail)*);
     };
-    (function -> @$path:literal :: $func:ident , $($tail:tt)*) => {
-        $crate::proc_macro!($func -> @$path::$func);
-        $crate::macros!($($tail)*);
+
+    // -------------------------------------------------
+    // splitting entries
+    // -------------------------------------------------
+
+    // entries are cut off the ones after them in a single step, up to four at a time, and
+    // expanded on their own, so that an invocation only recurses once every few entries. As
+    // `macro_rules` can't match a run of identifiers followed by another one, all the ones
+    // leading a path are recorded as seen rather than only its root, so a module named like a
+    // marker, or already brought in by a `use` entry, isn't declared again
+    (@scope [$($seen:tt)*] $cfgs:tt $prefix:tt $stack:tt
+        $(#[$($a_meta:tt)*])* $a_kind:ident $(($($a_spec:tt)*))? -> $($a_path:ident)+ $(:: $a_more:tt)*,
+        $(#[$($b_meta:tt)*])* $b_kind:ident $(($($b_spec:tt)*))? -> $($b_path:ident)+ $(:: $b_more:tt)*,
+        $(#[$($c_meta:tt)*])* $c_kind:ident $(($($c_spec:tt)*))? -> $($c_path:ident)+ $(:: $c_more:tt)*,
+        $(#[$($d_meta:tt)*])* $d_kind:ident $(($($d_spec:tt)*))? -> $($d_path:ident)+ $(:: $d_more:tt)* $(, $($tail:tt)*)?
+    ) => {
+        $crate::macros!(@spec [[$($seen)*] [] $prefix []] $cfgs $(#[$($a_meta)*])* $a_kind $(($($a_spec)*))? -> $($a_path)+$(::$a_more)*);
+        $crate::macros!(@spec [[$($seen)* $({$a_path})+] [] $prefix []] $cfgs $(#[$($b_meta)*])* $b_kind $(($($b_spec)*))? -> $($b_path)+$(::$b_more)*);
+        $crate::macros!(@spec [[$($seen)* $({$a_path})+ $({$b_path})+] [] $prefix []] $cfgs $(#[$($c_meta)*])* $c_kind $(($($c_spec)*))? -> $($c_path)+$(::$c_more)*);
+        $crate::macros!(@spec [[$($seen)* $({$a_path})+ $({$b_path})+ $({$c_path})+] [] $prefix []] $cfgs $(#[$($d_meta)*])* $d_kind $(($($d_spec)*))? -> $($d_path)+$(::$d_more)*);
+        $crate::macros!(@scope [$($seen)* $({$a_path})+ $({$b_path})+ $({$c_path})+ $({$d_path})+] $cfgs $prefix $stack $($($tail)*)?);
+    };
+    (@scope [$($seen:tt)*] $cfgs:tt $prefix:tt $stack:tt
+        $(#[$($a_meta:tt)*])* $a_kind:ident $(($($a_spec:tt)*))? -> $($a_path:ident)+ $(:: $a_more:tt)*,
+        $(#[$($b_meta:tt)*])* $b_kind:ident $(($($b_spec:tt)*))? -> $($b_path:ident)+ $(:: $b_more:tt)* $(, $($tail:tt)*)?
+    ) => {
+        $crate::macros!(@spec [[$($seen)*] [] $prefix []] $cfgs $(#[$($a_meta)*])* $a_kind $(($($a_spec)*))? -> $($a_path)+$(::$a_more)*);
+        $crate::macros!(@spec [[$($seen)* $({$a_path})+] [] $prefix []] $cfgs $(#[$($b_meta)*])* $b_kind $(($($b_spec)*))? -> $($b_path)+$(::$b_more)*);
+        $crate::macros!(@scope [$($seen)* $({$a_path})+ $({$b_path})+] $cfgs $prefix $stack $($($tail)*)?);
+    };
+    (@scope [$($seen:tt)*] $cfgs:tt $prefix:tt $stack:tt $(#[$($a_meta:tt)*])* $a_kind:ident $(($($a_spec:tt)*))? -> $($a_path:ident)+ $(:: $a_more:tt)* $(, $($tail:tt)*)?) => {
+        $crate::macros!(@spec [[$($seen)*] [] $prefix []] $cfgs $(#[$($a_meta)*])* $a_kind $(($($a_spec)*))? -> $($a_path)+$(::$a_more)*);
+        $crate::macros!(@scope [$($seen)* $({$a_path})+] $cfgs $prefix $stack $($($tail)*)?);
+    };
+    // files, declared as a named module or not
+    (@scope [$($seen:tt)*] $cfgs:tt $prefix:tt $stack:tt $(#[$($meta:tt)*])* $kind:ident $(($($spec:tt)*))? -> $($marker:ident)* $path:literal as $module:ident :: $func:ident $(, $($tail:tt)*)?) => {
+        $crate::macros!(@spec [[$($seen)*] [] $prefix []] $cfgs $(#[$($meta)*])* $kind $(($($spec)*))? -> $($marker)* $path as $module :: $func);
+        $crate::macros!(@scope [$($seen)* {$module}] $cfgs $prefix $stack $($($tail)*)?);
+    };
+    (@scope [$($seen:tt)*] $cfgs:tt $prefix:tt $stack:tt $(#[$($meta:tt)*])* $kind:ident $(($($spec:tt)*))? -> $($marker:ident)* @ $anchor:tt $(/ $path:literal)? as $module:ident :: $func:ident $(, $($tail:tt)*)?) => {
+        $crate::macros!(@spec [[$($seen)*] [] $prefix []] $cfgs $(#[$($meta)*])* $kind $(($($spec)*))? -> $($marker)* @ $anchor $(/ $path)? as $module :: $func);
+        $crate::macros!(@scope [$($seen)* {$module}] $cfgs $prefix $stack $($($tail)*)?);
+    };
+    (@scope $seen:tt $cfgs:tt $prefix:tt $stack:tt $(#[$($meta:tt)*])* $kind:ident $(($($spec:tt)*))? -> $($marker:ident)* $path:literal :: $func:ident $(, $($tail:tt)*)?) => {
+        $crate::macros!(@spec [$seen [] $prefix []] $cfgs $(#[$($meta)*])* $kind $(($($spec)*))? -> $($marker)* $path :: $func);
+        $crate::macros!(@scope $seen $cfgs $prefix $stack $($($tail)*)?);
+    };
+    (@scope $seen:tt $cfgs:tt $prefix:tt $stack:tt $(#[$($meta:tt)*])* $kind:ident $(($($spec:tt)*))? -> $($marker:ident)* @ $anchor:tt $(/ $path:literal)? :: $func:ident $(, $($tail:tt)*)?) => {
+        $crate::macros!(@spec [$seen [] $prefix []] $cfgs $(#[$($meta)*])* $kind $(($($spec)*))? -> $($marker)* @ $anchor $(/ $path)? :: $func);
+        $crate::macros!(@scope $seen $cfgs $prefix $stack $($($tail)*)?);
+    };
+    // the others (`module::prefix*` and malformed implementations) are cut token by token
+    (@scope $seen:tt $cfgs:tt $prefix:tt $stack:tt $(#[$($meta:tt)*])* $kind:ident $(($($spec:tt)*))? -> $($rest:tt)+) => {
+        $crate::macros!(@cut [$seen $cfgs $prefix $stack] [$(#[$($meta)*])* $kind $(($($spec)*))? ->] $($rest)+);
     };
-    (function($name:ident) -> $func:ident , $($tail:tt)*) => {
-        $crate::proc_macro!($name -> $func);
-        $crate::macros!($($tail)*);
+
+    // common mistakes, which would otherwise only be reported as a failure to match
+    (@scope $seen:tt $cfgs:tt [$($prefix:ident)::+] $stack:tt $(#[cfg($($cfg:tt)*)])* mod $($rest:tt)*) => {
+        $crate::macros!(
+            @malformed [$seen $cfgs [$($prefix)::+] $stack]
+            ["`mod` groups can only be used at the top level, groups nested in another one refer to a module of the enclosing group"]
+            [] mod $($rest)*
+        );
+    };
+    (@scope $seen:tt $cfgs:tt $prefix:tt $stack:tt $(#[$($meta:tt)*])+ $($group:ident)+ $(:: $rest:ident)* { $($inner:tt)* } $($tail:tt)*) => {
+        $crate::macros!(
+            @malformed [$seen $cfgs $prefix $stack]
+            ["only `#[cfg(...)]` attributes can be applied to a group of entries"]
+            [$(#[$($meta)*])+ $($group)+ $(:: $rest)* { .. }] $($tail)*
+        );
+    };
+    (@scope $seen:tt $cfgs:tt $prefix:tt $stack:tt $(#[$($meta:tt)*])* $kind:ident ($($spec:tt)*) $($rest:tt)*) => {
+        $crate::macros!(
+            @malformed [$seen $cfgs $prefix $stack]
+            ["expected `->` between `", stringify!($kind($($spec)*)), "` and the implementation"]
+            [] $kind($($spec)*) $($rest)*
+        );
+    };
+    (@scope $seen:tt $cfgs:tt $prefix:tt $stack:tt $(#[$($meta:tt)*])* $kind:ident $($rest:tt)*) => {
+        $crate::macros!(
+            @malformed [$seen $cfgs $prefix $stack]
+            ["expected `->` between `", stringify!($kind), "` and the implementation"]
+            [] $kind $($rest)*
+        );
+    };
+    (@scope $seen:tt $cfgs:tt $prefix:tt $stack:tt $($rest:tt)+) => {
+        $crate::macros!(@malformed [$seen $cfgs $prefix $stack] ["expected the kind of macro (`function`, `attribute` or `derive`)"] [] $($rest)+);
     };
-    (function($name:ident) -> $module:ident :: $func:ident , $($tail:tt)*) => {
-        $crate::proc_macro!($name -> $module::$func);
-        $crate::macros!($($tail)*);
+
+    (@cut [$seen:tt $cfgs:tt $prefix:tt $stack:tt] [$($entry:tt)*] , $($tail:tt)*) => {
+        $crate::macros!(@spec [$seen [] $prefix []] $cfgs $($entry)*);
+        $crate::macros!(@scope $seen $cfgs $prefix $stack $($tail)*);
     };
-    (function($name:ident) -> @$path:literal :: $func:ident , $($tail:tt)*) => {
-        $crate::proc_macro!($name -> @$path::$func);
-        $crate::macros!($($tail)*);
+    (@cut $state:tt [$($entry:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::macros!(@cut $state [$($entry)* $next] $($rest)*);
     };
-    (function($name:ident) -> use $func:ident , $($tail:tt)*) => {
-        $crate::proc_macro!($name -> use $func);
-        $crate::macros!($($tail)*);
+    (@cut $state:tt $entry:tt) => {
+        $crate::macros!(@cut $state $entry ,);
     };
-    (function($name:ident) -> use $module:ident :: $func:ident , $($tail:tt)*) => {
-        $crate::proc_macro!($name -> use $module::$func);
-        $crate::macros!($($tail)*);
+
+    // -------------------------------------------------
+    // kinds
+    // -------------------------------------------------
+
+    // an entry on its own, along with the module roots seen before it and the prefix of its
+    // group (the state its malformed parts are reported with)
+    (@spec $state:tt [$($cfgs:tt)*] $(#[$($meta:tt)*])* $kind:ident $(($name:ident))? -> $($rest:tt)+) => {
+        $crate::macros!(@kind $state [$($cfgs)* $(#[$($meta)*])*] [$kind $(($name))?] $($rest)+);
+    };
+    (@spec $state:tt [$($cfgs:tt)*] $(#[$($meta:tt)*])* $kind:ident ($($spec:tt)*) -> $($rest:tt)+) => {
+        $crate::macros!(@options $state [$($cfgs)* $(#[$($meta)*])*] $kind [] [$($spec)*] $($rest)+);
+    };
+
+    // the options trailing the spec (`, alias(..)` and `, deprecated("..")`, in either order)
+    // are split off it, which takes a step per token, as the types of the spec can't be told
+    // apart from them otherwise
+    (@options $state:tt $meta:tt $kind:ident [$($spec:tt)*] [, alias($($alias:ident),* $(,)?)] $($rest:tt)+) => {
+        $crate::macros!(@kind $state $meta [$kind ($($spec)*) alias($($alias),*)] $($rest)+);
     };
-    (function($name:ident) -> mod $func:ident , $($tail:tt)*) => {
-        $crate::proc_macro!($name -> mod $func);
-        $crate::macros!($($tail)*);
+    (@options $state:tt $meta:tt $kind:ident [$($spec:tt)*] [, deprecated($note:literal)] $($rest:tt)+) => {
+        $crate::macros!(@kind $state $meta [$kind ($($spec)*) deprecated($note)] $($rest)+);
     };
-    (function($name:ident) -> mod $module:ident :: $func:ident , $($tail:tt)*) => {
-        $crate::proc_macro!($name -> mod $module::$func);
-        $crate::macros!($($tail)*);
+    (@options $state:tt $meta:tt $kind:ident [$($spec:tt)*] [, alias($($alias:ident),* $(,)?), deprecated($note:literal)] $($rest:tt)+) => {
+        $crate::macros!(@kind $state $meta [$kind ($($spec)*) alias($($alias),*) deprecated($note)] $($rest)+);
     };
-    (function($name:ident) -> $path:literal :: $func:ident , $($tail:tt)*) => {
-        $crate::proc_macro!($name -> $path::$func);
-        $crate::macros!($($tail)*);
+    (@options $state:tt $meta:tt $kind:ident [$($spec:tt)*] [, deprecated($note:literal), alias($($alias:ident),* $(,)?)] $($rest:tt)+) => {
+        $crate::macros!(@kind $state $meta [$kind ($($spec)*) alias($($alias),*) deprecated($note)] $($rest)+);
+    };
+    (@options $state:tt $meta:tt $kind:ident [$($spec:tt)*] [$next:tt $($more:tt)*] $($rest:tt)+) => {
+        $crate::macros!(@options $state $meta $kind [$($spec)* $next] [$($more)*] $($rest)+);
+    };
+    (@options $state:tt $meta:tt $kind:ident [$($spec:tt)*] [] $($rest:tt)+) => {
+        $crate::macros!(@kind $state $meta [$kind ($($spec)*)] $($rest)+);
     };
 
-    // attribute patterns with trailing comma
-    (attribute -> $func:ident , $($tail:tt)*) => {
-        $crate::attr_macro!($func -> $func);
-        $crate::macros!($($tail)*);
+    (@kind $state:tt $meta:tt [function $(($name:ident $(: $($ty:tt)+)?) $(alias $aliases:tt)? $(deprecated $note:tt)?)?] $($rest:tt)+) => {
+        $crate::macros!(@entry $state $meta [function $(($name $(: $($ty)+)?) $(alias $aliases)? $(deprecated $note)?)?] [] $($rest)+);
+    };
+    (@kind $state:tt $meta:tt [attribute $(($name:ident $(: $($types:tt)+)?) $(alias $aliases:tt)? $(deprecated $note:tt)?)?] $($rest:tt)+) => {
+        $crate::macros!(@entry $state $meta [attribute $(($name $(: $($types)+)?) $(alias $aliases)? $(deprecated $note)?)?] [] $($rest)+);
+    };
+    (@kind $state:tt $meta:tt [derive $(($name:ident $(, attributes($($attr:ident),* $(,)?))?) $(alias $aliases:tt)? $(deprecated $note:tt)?)?] $($rest:tt)+) => {
+        $crate::macros!(@entry $state $meta [derive $(($name $(, attributes($($attr),*))?) $(alias $aliases)? $(deprecated $note)?)?] [] $($rest)+);
+    };
+    (@kind $state:tt $meta:tt [derive($name:ident : $($spec:tt)+) $(alias $aliases:tt)? $(deprecated $note:tt)?] $($rest:tt)+) => {
+        $crate::macros!(@entry $state $meta [derive($name : $($spec)+) $(alias $aliases)? $(deprecated $note)?] [] $($rest)+);
+    };
+    (@kind $state:tt $meta:tt [function $spec:tt $($options:tt)*] $($rest:tt)+) => {
+        $crate::macros!(
+            @malformed $state
+            ["malformed macro name `", stringify!($spec), "`, expected `function(name)` or `function(name: InputType)`"]
+            [function $spec ->] $($rest)+
+        );
+    };
+    (@kind $state:tt $meta:tt [attribute $spec:tt $($options:tt)*] $($rest:tt)+) => {
+        $crate::macros!(
+            @malformed $state
+            ["malformed macro name `", stringify!($spec), "`, expected `attribute(name)` or `attribute(name: ArgsType, ItemType)`"]
+            [attribute $spec ->] $($rest)+
+        );
+    };
+    (@kind $state:tt $meta:tt [derive $spec:tt $($options:tt)*] $($rest:tt)+) => {
+        $crate::macros!(
+            @malformed $state
+            ["malformed derive name `", stringify!($spec), "`, expected `derive(Name)`, `derive(Name: InputType)` or either followed by `, attributes(helper, ..)`"]
+            [derive $spec ->] $($rest)+
+        );
+    };
+    (@kind $state:tt $meta:tt [$kind:ident $($spec:tt)*] $($rest:tt)+) => {
+        $crate::macros!(
+            @malformed $state
+            ["unknown macro kind `", stringify!($kind), "`, expected `function`, `attribute` or `derive`"]
+            [$kind $($spec)* ->] $($rest)+
+        );
     };
-    (attribute -> $module:ident :: $func:ident , $($tail:tt)*) => {
-        $crate::attr_macro!($func -> $module::$func);
-        $crate::macros!($($tail)*);
+
+    // -------------------------------------------------
+    // entries
+    // -------------------------------------------------
+
+    (@entry [$seen:tt $cfgs:tt [] $stack:tt] $meta:tt $kind:tt [$($pre:tt)*] use $($path:ident)::+) => {
+        $crate::macros!(@emit $meta $kind [$($pre)* use $($path)::+] [$($path)*]);
     };
-    (attribute -> @$path:literal :: $func:ident , $($tail:tt)*) => {
-        $crate::attr_macro!($func -> @$path::$func);
-        $crate::macros!($($tail)*);
+    // module roots are declared by the first entry referring to them, and then `use`d, while
+    // the implementation as written is passed on (in brackets) for the diagnostics naming it
+    (@entry [[$($seen:tt)*] $cfgs:tt [] $stack:tt] $meta:tt $kind:tt [$($pre:tt)*] mod $first:ident $(:: $rest:ident)+) => {
+        $crate::macros!(@declare $first {mod $first;} $meta [$($seen)*] $);
+        $crate::macros!(@emit $meta $kind [$($pre)* [mod $first $(:: $rest)+] use $first $(:: $rest)+] [$first $($rest)*]);
     };
-    (attribute($name:ident) -> $func:ident , $($tail:tt)*) => {
-        $crate::attr_macro!($name -> $func);
-        $crate::macros!($($tail)*);
+    (@entry $state:tt $meta:tt $kind:tt $pre:tt mod $first:ident $(:: $rest:ident)+) => {
+        $crate::macros!(@entry $state $meta $kind $pre $first $(:: $rest)+);
     };
-    (attribute($name:ident) -> $module:ident :: $func:ident , $($tail:tt)*) => {
-        $crate::attr_macro!($name -> $module::$func);
-        $crate::macros!($($tail)*);
+    (@entry [[$($seen:tt)*] $cfgs:tt [] $stack:tt] $meta:tt $kind:tt [$($pre:tt)*] $first:ident $(:: $rest:ident)+) => {
+        $crate::macros!(@declare $first {mod $first;} $meta [$($seen)*] $);
+        $crate::macros!(@emit $meta $kind [$($pre)* [$first $(:: $rest)+] use $first $(:: $rest)+] [$first $($rest)*]);
     };
-    (attribute($name:ident) -> @$path:literal :: $func:ident , $($tail:tt)*) => {
-        $crate::attr_macro!($name -> @$path::$func);
-        $crate::macros!($($tail)*);
+    (@entry [$seen:tt $cfgs:tt [] $stack:tt] $meta:tt $kind:tt [$($pre:tt)*] $func:ident) => {
+        $crate::macros!(@emit $meta $kind [$($pre)* $func] [$func]);
     };
-
-    // attribute use patterns with trailing comma
-    (attribute($name:ident) -> use $func:ident , $($tail:tt)*) => {
-        $crate::attr_macro!($name -> use $func);
-        $crate::macros!($($tail)*);
+    // inside a group, paths are relative to the group's (already declared) module
+    (@entry [$seen:tt $cfgs:tt [$($prefix:ident)::+] $stack:tt] $meta:tt $kind:tt [$($pre:tt)*] $($path:ident)::+) => {
+        $crate::macros!(@emit $meta $kind [$($pre)* [$($path)::+] use $($prefix ::)+ $($path)::+] [$($path)*]);
     };
-    (attribute($name:ident) -> use $module:ident :: $func:ident , $($tail:tt)*) => {
-        $crate::attr_macro!($name -> use $module::$func);
-        $crate::macros!($($tail)*);
+    // named file modules are declared by the first entry referring to them as well, wherever
+    // it is
+    (@entry [[$($seen:tt)*] $($state:tt)*] $meta:tt $kind:tt [$($pre:tt)*] $path:literal as $module:ident :: $func:ident) => {
+        $crate::macros!(@declare $module {$crate::__delegate!(@module $module $path);} $meta [$($seen)*] $);
+        $crate::macros!(@emit $meta $kind [$($pre)* [$path as $module::$func] use $module::$func] [$func]);
     };
-    (attribute($name:ident) -> mod $func:ident , $($tail:tt)*) => {
-        $crate::attr_macro!($name -> mod $func);
-        $crate::macros!($($tail)*);
+    (@entry [[$($seen:tt)*] $($state:tt)*] $meta:tt $kind:tt [$($pre:tt)*] @$path:literal as $module:ident :: $func:ident) => {
+        $crate::macros!(@declare $module {$crate::__delegate!(@module $module @$path);} $meta [$($seen)*] $);
+        $crate::macros!(@emit $meta $kind [$($pre)* [@$path as $module::$func] use $module::$func] [$func]);
     };
-    (attribute($name:ident) -> mod $module:ident :: $func:ident , $($tail:tt)*) => {
-        $crate::attr_macro!($name -> mod $module::$func);
-        $crate::macros!($($tail)*);
+    (@entry $state:tt $meta:tt $kind:tt $pre:tt @glob / $path:literal as $module:ident :: $func:ident) => {
+        $crate::__delegate!(@module $module @glob / $path);
     };
-
-    (derive($name:ident, attributes($($attr:ident),*)) -> $module:ident :: $func:ident , $($tail:tt)*) => {
-        $crate::derive_macro!(($name, attributes($($attr),*)) -> $module::$func);
-        $crate::macros!($($tail)*);
+    (@entry [[$($seen:tt)*] $($state:tt)*] $meta:tt $kind:tt [$($pre:tt)*] @ $anchor:tt / $path:literal as $module:ident :: $func:ident) => {
+        $crate::macros!(@declare $module {$crate::__delegate!(@module $module @ $anchor / $path);} $meta [$($seen)*] $);
+        $crate::macros!(@emit $meta $kind [$($pre)* [@ $anchor / $path as $module::$func] use $module::$func] [$func]);
     };
-
-    (derive($name:ident, attributes($($attr:ident),*)) -> $module:ident :: $func:ident) => {
-        $crate::derive_macro!(($name, attributes($($attr),*)) -> $module::$func);
+    (@entry $state:tt $meta:tt $kind:tt [$($pre:tt)*] $path:literal :: $func:ident) => {
+        $crate::macros!(@emit $meta $kind [$($pre)* $path :: $func] [$func]);
     };
-
-    (derive($name:ident, attributes($($attr:ident),+)) -> $module:ident :: $func:ident , $($tail:tt)*) => {
-        $crate::derive_macro!(($name, attributes($($attr),+)) -> $module::$func);
-        $crate::macros!($($tail)*);
+    (@entry $state:tt $meta:tt $kind:tt [$($pre:tt)*] @$path:literal :: $func:ident) => {
+        $crate::macros!(@emit $meta $kind [$($pre)* @$path :: $func] [$func]);
     };
-
-    (derive($name:ident, attributes($($attr:ident),+)) -> use $module:ident :: $func:ident , $($tail:tt)*) => {
-        $crate::derive_macro!(($name, attributes($($attr),+)) -> use $module::$func);
-        $crate::macros!($($tail)*);
+    // globs of crate-relative paths (`@glob/"src/*.rs"`), which only the frontend can expand
+    (@entry $state:tt $meta:tt $kind:tt $pre:tt @glob / $path:literal :: $func:ident) => {
+        $crate::__glob!($meta $kind $pre $path $func);
     };
-    (derive($name:ident, attributes($($attr:ident),+)) -> mod $module:ident :: $func:ident , $($tail:tt)*) => {
-        $crate::derive_macro!(($name, attributes($($attr),+)) -> mod $module::$func);
-        $crate::macros!($($tail)*);
+    // paths anchored elsewhere than the crate root (`@out/"file.rs"`)
+    (@entry $state:tt $meta:tt $kind:tt [$($pre:tt)*] @ $anchor:tt / $path:literal :: $func:ident) => {
+        $crate::macros!(@emit $meta $kind [$($pre)* @ $anchor / $path :: $func] [$func]);
     };
-
-    // derive patterns with trailing comma
-    (derive($name:ident) -> $func:ident , $($tail:tt)*) => {
-        $crate::derive_macro!(($name) -> $func);
-        $crate::macros!($($tail)*);
+    // all the public functions of a module (`module::*`, or `module::prefix*` for a subset)
+    (@entry $state:tt $meta:tt $kind:tt $pre:tt use $first:ident :: $($rest:tt)+) => {
+        $crate::macros!(@bulk [use] $state $meta $kind $pre [$first] $($rest)+);
     };
-    (derive($name:ident) -> $module:ident :: $func:ident , $($tail:tt)*) => {
-        $crate::derive_macro!(($name) -> $module::$func);
-        $crate::macros!($($tail)*);
+    (@entry $state:tt $meta:tt $kind:tt $pre:tt mod $first:ident :: $($rest:tt)+) => {
+        $crate::macros!(@bulk [mod] $state $meta $kind $pre [$first] $($rest)+);
     };
-    (derive($name:ident) -> @$path:literal :: $func:ident , $($tail:tt)*) => {
-        $crate::derive_macro!(($name) -> @$path::$func);
-        $crate::macros!($($tail)*);
+    (@entry $state:tt $meta:tt $kind:tt $pre:tt $first:ident :: $($rest:tt)+) => {
+        $crate::macros!(@bulk [] $state $meta $kind $pre [$first] $($rest)+);
     };
-
-    (derive($name:ident) -> use $func:ident , $($tail:tt)*) => {
-        $crate::derive_macro!(($name) -> use $func);
-        $crate::macros!($($tail)*);
+    // markers preceding the implementation path
+    (@entry $state:tt $meta:tt $kind:tt [$($pre:tt)*] pm2 $($rest:tt)+) => {
+        $crate::macros!(@entry $state $meta $kind [$($pre)* pm2] $($rest)+);
+    };
+    (@entry $state:tt $meta:tt $kind:tt [$($pre:tt)*] try $($rest:tt)+) => {
+        $crate::macros!(@entry $state $meta $kind [$($pre)* try] $($rest)+);
+    };
+    (@entry $state:tt $meta:tt $kind:tt [$($pre:tt)*] catch $($rest:tt)+) => {
+        $crate::macros!(@entry $state $meta $kind [$($pre)* catch] $($rest)+);
     };
-    (derive($name:ident) -> use $module:ident :: $func:ident , $($tail:tt)*) => {
-        $crate::derive_macro!(($name) -> use $module::$func);
-        $crate::macros!($($tail)*);
+    (@entry $state:tt $meta:tt [$($kind:tt)*] [$($pre:tt)*] mod $($rest:tt)*) => {
+        $crate::macros!(@malformed $state ["unsupported implementation"] [$($kind)* -> $($pre)* mod] $($rest)*);
     };
-    (derive($name:ident) -> mod $func:ident , $($tail:tt)*) => {
-        $crate::derive_macro!(($name) -> mod $func);
-        $crate::macros!($($tail)*);
+    (@entry $state:tt $meta:tt [$($kind:tt)*] [$($pre:tt)*] use $($rest:tt)*) => {
+        $crate::macros!(@malformed $state ["unsupported implementation"] [$($kind)* -> $($pre)* use] $($rest)*);
     };
-    (derive($name:ident) -> mod $module:ident :: $func:ident , $($tail:tt)*) => {
-        $crate::derive_macro!(($name) -> mod $module::$func);
-        $crate::macros!($($tail)*);
+    (@entry $state:tt $meta:tt [$($kind:tt)*] [$($pre:tt)*] $marker:ident $($rest:tt)+) => {
+        $crate::macros!(
+            @malformed $state
+            ["expected `pm2`, `try` or `catch` before the implementation, found `", stringify!($marker), "`"]
+            [$($kind)* -> $($pre)*] $marker $($rest)+
+        );
     };
-    (derive($name:ident, attributes($($attr:ident),+)) -> $func:ident , $($tail:tt)*) => {
-        $crate::derive_macro!(($name, attributes($($attr),+)) -> $func);
-        $crate::macros!($($tail)*);
+    (@entry $state:tt $meta:tt [$($kind:tt)*] [$($pre:tt)*] $($rest:tt)*) => {
+        $crate::macros!(@malformed $state ["unsupported implementation"] [$($kind)* -> $($pre)*] $($rest)*);
     };
-    (derive($name:ident, attributes($($attr:ident),+)) -> @$path:literal :: $func:ident , $($tail:tt)*) => {
-        $crate::derive_macro!(($name, attributes($($attr),+)) -> @$path::$func);
-        $crate::macros!($($tail)*);
+
+    // -------------------------------------------------
+    // bulk registration
+    // -------------------------------------------------
+
+    (@bulk $decl:tt $state:tt $meta:tt $kind:tt $pre:tt [$($module:ident)+] $next:ident :: $($rest:tt)+) => {
+        $crate::macros!(@bulk $decl $state $meta $kind $pre [$($module)+ $next] $($rest)+);
     };
-    (derive($name:ident, attributes($($attr:ident),+)) -> use $func:ident , $($tail:tt)*) => {
-        $crate::derive_macro!(($name, attributes($($attr),+)) -> use $func);
-        $crate::macros!($($tail)*);
+    (@bulk $decl:tt $state:tt $meta:tt $kind:tt $pre:tt $module:tt *) => {
+        $crate::macros!(@bulk $decl $state $meta $kind $pre $module []);
     };
-    (derive($name:ident, attributes($($attr:ident),+)) -> mod $func:ident , $($tail:tt)*) => {
-        $crate::derive_macro!(($name, attributes($($attr),+)) -> mod $func);
-        $crate::macros!($($tail)*);
+    (@bulk $decl:tt $state:tt $meta:tt $kind:tt $pre:tt $module:tt $filter:ident *) => {
+        $crate::macros!(@bulk $decl $state $meta $kind $pre $module [$filter]);
     };
 
-    // terminal patterns (without trailing comma)
-    (function -> $func:ident) => {
-        $crate::proc_macro!($func -> $func);
+    // the functions are listed by the frontend, but the module is declared here, like for
+    // any other entry
+    (@bulk [$(mod)?] [[$($seen:tt)*] $cfgs:tt [] $stack:tt] $meta:tt $kind:tt $pre:tt [$first:ident $($module:ident)*] $filter:tt) => {
+        $crate::macros!(@declare $first {mod $first;} $meta [$($seen)*] $);
+        $crate::__bulk!($meta $kind $pre [$first $(:: $module)*] $filter);
     };
-    (function -> $module:ident :: $func:ident) => {
-        $crate::proc_macro!($func -> $module::$func);
+    (@bulk [use] [$seen:tt $cfgs:tt [] $stack:tt] $meta:tt $kind:tt $pre:tt [$($module:ident)+] $filter:tt) => {
+        $crate::__bulk!($meta $kind $pre [$($module)::+] $filter);
     };
-    (function -> @$path:literal :: $func:ident) => {
-        $crate::proc_macro!($func -> @$path::$func);
+    (@bulk $decl:tt [$seen:tt $cfgs:tt [$($prefix:ident)::+] $stack:tt] $meta:tt $kind:tt $pre:tt [$($module:ident)+] $filter:tt) => {
+        $crate::__bulk!($meta $kind $pre [$($prefix ::)+ $($module)::+] $filter);
     };
-    (function($name:ident) -> $func:ident) => {
-        $crate::proc_macro!($name -> $func);
+    (@bulk [$($decl:tt)*] $state:tt $meta:tt [$($kind:tt)*] [$($pre:tt)*] [$($module:ident)+] $($rest:tt)*) => {
+        $crate::macros!(
+            @malformed $state ["unsupported implementation"]
+            [$($kind)* -> $($pre)* $($decl)* $($module)::+ ::] $($rest)*
+        );
     };
-    (function($name:ident) -> $module:ident :: $func:ident) => {
-        $crate::proc_macro!($name -> $module::$func);
+
+    // -------------------------------------------------
+    // malformed entries
+    // -------------------------------------------------
+
+    // reports the entry (up to the next comma) and carries on with the next one
+    (@malformed [$($state:tt)*] [$($reason:tt)*] [$($entry:tt)*] , $($tail:tt)*) => {
+        compile_error!(concat!(
+            "malformed `macros!` entry `", stringify!($($entry)*), "`: ", $($reason)*, "\n\n",
+            "entries look like `kind -> implementation` or `kind(spec) -> implementation`, separated by commas, ",
+            "and can be grouped as `module { entries }`, where:\n",
+            "- the kind is `function`, `attribute` or `derive`\n",
+            "- the spec is the name of the macro, optionally with the types to parse the input as (`function(name: InputType)`, ",
+            "`attribute(name: ArgsType, ItemType)` or `derive(Name: InputType)`), followed by `, attributes(helper, ..)` for derives, ",
+            "then `, alias(other, ..)` and `, deprecated(\"note\")`\n",
+            "- the implementation is `function`, `module::function`, `mod module::function`, `use module::function`, ",
+            "`module::*`, `module::prefix*`, `\"file.rs\"::function`, `@\"file.rs\"::function`, `@glob/\"dir/*.rs\"::function`, ",
+            "`@out/\"file.rs\"::function`, `@workspace/\"file.rs\"::function` or `@\"ENV_VAR\"/\"file.rs\"::function`, ",
+            "where any of the files but a glob can be declared as a module (e.g. `@out/\"file.rs\" as module::function`), ",
+            "optionally preceded by `pm2`, `try` and `catch`",
+        ));
+        $crate::macros!(@scope $($state)* $($tail)*);
+    };
+    (@malformed $state:tt $reason:tt [$($entry:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::macros!(@malformed $state $reason [$($entry)* $next] $($rest)*);
+    };
+    (@malformed $state:tt $reason:tt $entry:tt) => {
+        $crate::macros!(@malformed $state $reason $entry ,);
     };
-    (function($name:ident) -> @$path:literal :: $func:ident) => {
-        $crate::proc_macro!($name -> @$path::$func);
+
+    // -------------------------------------------------
+    // module declarations
+    // -------------------------------------------------
+
+    // the frontend declares the module roots itself
+    (@declare $root:ident $item:tt $attrs:tt [frontend $($seen:tt)*] $d:tt) => {};
+
+    // declares the module unless an earlier entry has, through a local macro with an arm per
+    // root seen so far, as `macro_rules` can't compare identifiers otherwise
+    (@declare $root:ident $item:tt $attrs:tt [$($seen:tt)*] $d:tt) => {
+        macro_rules! __include_proc_macro_declare {
+            $(($seen) => {};)*
+            ({$d root:ident}) => {
+                $crate::macros!(@gates $item [] $attrs);
+            };
+        }
+        __include_proc_macro_declare!({$root});
+    };
+
+    // the module is compiled along with the entry declaring it, so only its cfgs are kept
+    (@gates {$($item:tt)*} [$($gate:tt)*] []) => {
+        $($gate)*
+        $($item)*
     };
-    (function($name:ident) -> use $func:ident) => {
-        $crate::proc_macro!($name -> use $func);
+    (@gates $item:tt [$($gate:tt)*] [#[cfg($($cfg:tt)*)] $($attr:tt)*]) => {
+        $crate::macros!(@gates $item [$($gate)* #[cfg($($cfg)*)]] [$($attr)*]);
     };
-    (function($name:ident) -> use $module:ident :: $func:ident) => {
-        $crate::proc_macro!($name -> use $module::$func);
+    (@gates $item:tt $gate:tt [#[$($meta:tt)*] $($attr:tt)*]) => {
+        $crate::macros!(@gates $item $gate [$($attr)*]);
     };
-    (function($name:ident) -> mod $func:ident) => {
-        $crate::proc_macro!($name -> mod $func);
+
+    // -------------------------------------------------
+    // delegation
+    // -------------------------------------------------
+
+    // implicit names are taken from the last segment of the implementation path
+    (@emit $meta:tt [$kind:ident] $impl:tt [$func:ident]) => {
+        $crate::macros!(@emit $meta [$kind($func)] $impl [$func]);
     };
-    (function($name:ident) -> mod $module:ident :: $func:ident) => {
-        $crate::proc_macro!($name -> mod $module::$func);
+    (@emit $meta:tt [$kind:ident] $impl:tt [$first:ident $($rest:ident)+]) => {
+        $crate::macros!(@emit $meta [$kind] $impl [$($rest)+]);
     };
 
-    (attribute -> $func:ident) => {
-        $crate::attr_macro!($func -> $func);
+    (@emit [$($meta:tt)*] [function($name:ident) $(alias $aliases:tt)? $(deprecated $note:tt)?] [$($impl:tt)+] $segments:tt) => {
+        $crate::proc_macro!($($meta)* ($name $(, alias $aliases)? $(, deprecated $note)?) -> $($impl)+);
     };
-    (attribute -> $module:ident :: $func:ident) => {
-        $crate::attr_macro!($func -> $module::$func);
+    (@emit [$($meta:tt)*] [function($name:ident : $($ty:tt)+) $(alias $aliases:tt)? $(deprecated $note:tt)?] [$($impl:tt)+] $segments:tt) => {
+        $crate::proc_macro!($($meta)* ($name : $($ty)+ $(, alias $aliases)? $(, deprecated $note)?) -> $($impl)+);
     };
-    (attribute -> @$path:literal :: $func:ident) => {
-        $crate::attr_macro!($func -> @$path::$func);
+    (@emit [$($meta:tt)*] [attribute($name:ident) $(alias $aliases:tt)? $(deprecated $note:tt)?] [$($impl:tt)+] $segments:tt) => {
+        $crate::attr_macro!($($meta)* ($name $(, alias $aliases)? $(, deprecated $note)?) -> $($impl)+);
     };
-    (attribute($name:ident) -> $func:ident) => {
-        $crate::attr_macro!($name -> $func);
+    (@emit [$($meta:tt)*] [attribute($name:ident : $($types:tt)+) $(alias $aliases:tt)? $(deprecated $note:tt)?] [$($impl:tt)+] $segments:tt) => {
+        $crate::attr_macro!($($meta)* ($name : $($types)+ $(, alias $aliases)? $(, deprecated $note)?) -> $($impl)+);
     };
-    (attribute($name:ident) -> $module:ident :: $func:ident) => {
-        $crate::attr_macro!($name -> $module::$func);
+    (@emit [$($meta:tt)*] [derive($($derive:tt)+) $(alias $aliases:tt)? $(deprecated $note:tt)?] [$($impl:tt)+] $segments:tt) => {
+        $crate::derive_macro!($($meta)* ($($derive)+ $(, alias $aliases)? $(, deprecated $note)?) -> $($impl)+);
     };
-    (attribute($name:ident) -> @$path:literal :: $func:ident) => {
-        $crate::attr_macro!($name -> @$path::$func);
+
+    ($(#[$($meta:tt)*])* $first:ident $($rest:tt)*) => {
+        $crate::__frontend!($(#[$($meta)*])* $first $($rest)*);
     };
-    (attribute($name:ident) -> use $func:ident) => {
-        $crate::attr_macro!($name -> use $func);
+    ($first:literal $($rest:tt)*) => {
+        $crate::__frontend!($first $($rest)*);
     };
-    (attribute($name:ident) -> use $module:ident :: $func:ident) => {
-        $crate::attr_macro!($name -> use $module::$func);
+}
+
+/// Errors that fallible (`try`) implementations can return.
+///
+/// When such an implementation returns `Err`, the generated entry point expands to
+/// whatever [`into_compile_error`](IntoCompileError::into_compile_error) produces
+/// instead, which is expected to be a `compile_error!` invocation. Implemented for
+/// `String` and `&str` (reported at the macro call site) and, with the `syn` feature,
+/// for `syn::Error` (reported at the spans it was created with).
+///
+/// See: [`compile_error`]
+pub trait IntoCompileError {
+    /// Converts the error into a `compile_error!` invocation.
+    fn into_compile_error(self) -> TokenStream;
+}
+
+impl IntoCompileError for String {
+    fn into_compile_error(self) -> TokenStream {
+        compile_error(&self, Span::call_site())
+    }
+}
+
+impl IntoCompileError for &str {
+    fn into_compile_error(self) -> TokenStream {
+        compile_error(self, Span::call_site())
+    }
+}
+
+#[cfg(feature = "syn")]
+impl IntoCompileError for syn::Error {
+    fn into_compile_error(self) -> TokenStream {
+        syn::Error::into_compile_error(self).into()
+    }
+}
+
+/// Builds a `compile_error!` invocation that reports `message` at `span`.
+///
+/// Handy for implementing [`IntoCompileError`] for custom error types.
+pub fn compile_error(message: &str, span: Span) -> TokenStream {
+    let tokens = [
+        TokenTree::from(Punct::new(':', Spacing::Joint)),
+        TokenTree::from(Punct::new(':', Spacing::Alone)),
+        TokenTree::from(Ident::new("core", span)),
+        TokenTree::from(Punct::new(':', Spacing::Joint)),
+        TokenTree::from(Punct::new(':', Spacing::Alone)),
+        TokenTree::from(Ident::new("compile_error", span)),
+        TokenTree::from(Punct::new('!', Spacing::Alone)),
+        TokenTree::from(Group::new(
+            Delimiter::Brace,
+            TokenTree::from(Literal::string(message)).into(),
+        )),
+    ];
+
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            token.set_span(span);
+            token
+        })
+        .collect()
+}
+
+/// The `proc_macro2::TokenStream` type, in the signatures expected of `pm2` implementations.
+#[cfg(feature = "proc-macro2")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pm2_token_stream {
+    () => {
+        $crate::__private::proc_macro2::TokenStream
     };
-    (attribute($name:ident) -> mod $func:ident) => {
-        $crate::attr_macro!($name -> mod $func);
+}
+
+/// Left to inference without the `proc-macro2` feature, which `pm2` implementations are
+/// reported to need when they're called.
+#[cfg(not(feature = "proc-macro2"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pm2_token_stream {
+    () => {
+        _
     };
-    (attribute($name:ident) -> mod $module:ident :: $func:ident) => {
-        $crate::attr_macro!($name -> mod $module::$func);
+}
+
+/// The arguments of an implementation taking parsed `syn` types, parsed (returning the error
+/// from the entry point if they can't be).
+#[cfg(feature = "syn")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __syn_arguments {
+    ([$($ty:ty),+] ($($arg:ident),+)) => {
+        ($(
+            match $crate::__private::syn::parse::<$ty>($arg) {
+                ::core::result::Result::Ok(parsed) => parsed,
+                ::core::result::Result::Err(err) => {
+                    return $crate::IntoCompileError::into_compile_error(err);
+                },
+            },
+        )+)
     };
+}
 
-    (derive($name:ident) -> $func:ident) => {
-        $crate::derive_macro!(($name) -> $func);
+/// The arguments of an implementation taking parsed `syn` types.
+#[cfg(not(feature = "syn"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __syn_arguments {
+    ($types:tt $args:tt) => {
+        compile_error!("typed inputs require the `syn` feature of `include_proc_macro`")
     };
-    (derive($name:ident) -> $module:ident :: $func:ident) => {
-        $crate::derive_macro!(($name) -> $module::$func);
+}
+
+/// Moves the tokens of a signature check onto the implementation of its entry, so that its
+/// errors point at it rather than at the whole invocation.
+#[cfg(feature = "frontend")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __spanned {
+    ($origin:tt $($tokens:tt)*) => {
+        $crate::__private::frontend::respan! { $origin $($tokens)* }
     };
-    (derive($name:ident) -> @$path:literal :: $func:ident) => {
-        $crate::derive_macro!(($name) -> @$path::$func);
+}
+
+/// Leaves the tokens of a signature check where they are (only the frontend can move them).
+#[cfg(not(feature = "frontend"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __spanned {
+    ($origin:tt $($tokens:tt)*) => {
+        $($tokens)*
     };
-    (derive($name:ident, attributes($($attr:ident),+)) -> $func:ident) => {
-        $crate::derive_macro!(($name, attributes($($attr),+)) -> $func);
+}
+
+/// Starts expanding a `macros!` invocation, parsed and checked by the frontend first.
+#[cfg(feature = "frontend")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __frontend {
+    ($($entries:tt)*) => {
+        $crate::__private::frontend::macros! { [$crate] $($entries)* }
     };
-    (derive($name:ident, attributes($($attr:ident),+)) -> $module:ident :: $func:ident) => {
-        $crate::derive_macro!(($name, attributes($($attr),+)) -> $module::$func);
+}
+
+/// Starts expanding a `macros!` invocation.
+#[cfg(not(feature = "frontend"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __frontend {
+    ($($entries:tt)*) => {
+        $crate::macros!(@scope [] [] [] [] $($entries)*);
     };
-    (derive($name:ident, attributes($($attr:ident),+)) -> @$path:literal :: $func:ident) => {
-        $crate::derive_macro!(($name, attributes($($attr),+)) -> @$path::$func);
+}
+
+/// Expands a `@glob/"..."` `macros!` entry, registering the matching files.
+#[cfg(feature = "frontend")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __glob {
+    ($($entry:tt)*) => {
+        $crate::__private::frontend::glob! { [$crate] $($entry)* }
     };
-    (derive($name:ident) -> use $func:ident) => {
-        $crate::derive_macro!(($name) -> use $func);
+}
+
+/// Expands a `@glob/"..."` `macros!` entry, registering the matching files.
+#[cfg(not(feature = "frontend"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __glob {
+    // the attributes of the entry still apply, so that it can be gated on the feature
+    ([$($meta:tt)*] $($entry:tt)*) => {
+        $($meta)*
+        const _: () = {
+            compile_error!(
+                "registering a macro per file matching a glob (`@glob/\"...\"`) requires the \
+                 `frontend` feature of `include_proc_macro`"
+            );
+        };
     };
-    (derive($name:ident) -> use $module:ident :: $func:ident) => {
-        $crate::derive_macro!(($name) -> use $module::$func);
+}
+
+/// Declares a file relative to the manifest directory as a module, from wherever the crate root
+/// is. The file is nested one level deeper than the module, so that its `super` still sees the
+/// items of the enclosing module without them clashing with its own in `$module`.
+#[cfg(feature = "frontend")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __crate_file {
+    ($module:ident $path:literal) => {
+        mod $module {
+            mod __dir {
+                #[allow(unused_imports)]
+                use super::super::*;
+                $crate::__private::frontend::crate_file! { $path }
+            }
+            pub use self::__dir::__file::*;
+        }
     };
-    (derive($name:ident, attributes($($attr:ident),+)) -> use $func:ident) => {
-        $crate::derive_macro!(($name, attributes($($attr),+)) -> use $func);
+}
+
+/// Includes a file relative to the manifest directory in a module (declaring it as a file
+/// module, with its own submodules, needs the `frontend` feature).
+#[cfg(not(feature = "frontend"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __crate_file {
+    ($module:ident $path:literal) => {
+        mod $module {
+            include!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $path));
+        }
     };
-    (derive($name:ident, attributes($($attr:ident),+)) -> use $module:ident :: $func:ident) => {
-        $crate::derive_macro!(($name, attributes($($attr),+)) -> use $module::$func);
+}
+
+/// The root of the workspace, for `@workspace/` paths, found from the manifests.
+#[cfg(feature = "frontend")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __workspace_dir {
+    () => {
+        $crate::__private::frontend::workspace_dir!()
     };
-    (derive($name:ident) -> mod $func:ident) => {
-        $crate::derive_macro!(($name) -> mod $func);
+}
+
+/// The root of the workspace, for `@workspace/` paths, which cargo doesn't tell, so it's read
+/// from `CARGO_WORKSPACE_DIR` (finding it from the manifests needs the `frontend` feature).
+#[cfg(not(feature = "frontend"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __workspace_dir {
+    () => {
+        env!(
+            "CARGO_WORKSPACE_DIR",
+            "`@workspace/` implementation paths need `CARGO_WORKSPACE_DIR` without the `frontend` \
+             feature, e.g. from `[env] CARGO_WORKSPACE_DIR = { value = \"\", relative = true }` \
+             in `.cargo/config.toml`"
+        )
     };
-    (derive($name:ident) -> mod $module:ident :: $func:ident) => {
-        $crate::derive_macro!(($name) -> mod $module::$func);
+}
+
+/// Expands a `module::*` `macros!` entry, registering the public functions of the module.
+#[cfg(feature = "frontend")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bulk {
+    ($($entry:tt)*) => {
+        $crate::__private::frontend::bulk! { [$crate] $($entry)* }
     };
-    (derive($name:ident, attributes($($attr:ident),+)) -> mod $func:ident) => {
-        $crate::derive_macro!(($name, attributes($($attr),+)) -> mod $func);
+}
+
+/// Expands a `module::*` `macros!` entry, registering the public functions of the module.
+#[cfg(not(feature = "frontend"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bulk {
+    // the attributes of the entry still apply, so that it can be gated on the feature
+    ([$($meta:tt)*] $($entry:tt)*) => {
+        $($meta)*
+        const _: () = {
+            compile_error!(
+                "registering all the functions of a module (`module::*`) requires the `frontend` \
+                 feature of `include_proc_macro`"
+            );
+        };
     };
-    (derive($name:ident, attributes($($attr:ident),+)) -> mod $module:ident :: $func:ident) => {
-        $crate::derive_macro!(($name, attributes($($attr),+)) -> mod $module::$func);
+}
+
+/// Defines the entry point of a derive as `__derive_Name`, leaving `Name` free at the crate
+/// root.
+#[cfg(feature = "frontend")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __entry_point {
+    ($($entry_point:tt)*) => {
+        $crate::__private::frontend::entry_point! { $($entry_point)* }
     };
 }
 
+/// Defines the entry point of a derive, named after the derive (renaming it needs the
+/// `frontend` feature).
+#[cfg(not(feature = "frontend"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __entry_point {
+    ([$prefix:ident] $($entry_point:tt)*) => {
+        $($entry_point)*
+    };
+}
+
+#[doc(hidden)]
+pub mod __private {
+    use std::panic::{self, AssertUnwindSafe};
+
+    #[cfg(feature = "frontend")]
+    pub use include_proc_macro_frontend as frontend;
+    #[cfg(feature = "proc-macro2")]
+    pub use proc_macro2;
+    #[cfg(feature = "syn")]
+    pub use syn;
+
+    use super::*;
+
+    /// Runs `expand`, reporting a panic as a `compile_error!` naming the macro and its
+    /// implementation instead of letting it abort the expansion.
+    pub fn catch_panic(
+        name: &str,
+        implementation: &str,
+        expand: impl FnOnce() -> TokenStream,
+    ) -> TokenStream {
+        panic::catch_unwind(AssertUnwindSafe(expand)).unwrap_or_else(|payload| {
+            let reason = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("unknown panic payload");
+            let implementation = written(implementation);
+
+            compile_error(
+                &format!("`{}` panicked in `{}`: {}", name, implementation, reason),
+                Span::call_site(),
+            )
+        })
+    }
+
+    /// An implementation path as it'd be written, from its stringified tokens: without the
+    /// spaces `stringify!` leaves around `::`, `/` and after `@` (outside of string literals).
+    fn written(implementation: &str) -> String {
+        let mut written = String::with_capacity(implementation.len());
+        let mut literal = false;
+        let mut chars = implementation.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '"' {
+                literal = !literal;
+            } else if c == ' ' && !literal {
+                let glued = written.ends_with("::") || written.ends_with(['/', '@']);
+                if glued || matches!(chars.peek(), Some(':' | '/')) {
+                    continue;
+                }
+            }
+            written.push(c);
+        }
+        written
+    }
+
+    /// The `deprecated("note")` option of an entry. Proc macros can't emit warnings, so a
+    /// reference to a `#[deprecated]` macro named after the entry's is added to the output
+    /// instead, which the compiler warns about where the output ends up.
+    pub struct Deprecation {
+        note: &'static str,
+        name: &'static str,
+        /// The span the warning points at, if not the invocation.
+        span: Option<Span>,
+        /// Whether the output may be an expression, rather than items only.
+        expression: bool,
+    }
+
+    impl Deprecation {
+        pub fn function(note: &'static str, name: &'static str) -> Self {
+            Self {
+                note,
+                name,
+                span: None,
+                expression: true,
+            }
+        }
+
+        pub fn attribute(note: &'static str, name: &'static str) -> Self {
+            Self {
+                note,
+                name,
+                span: None,
+                expression: false,
+            }
+        }
+
+        /// The warnings of the items a derive expands to are silenced, so the reference is
+        /// spanned at the name of the deriving item instead.
+        pub fn derive(note: &'static str, name: &'static str, input: &TokenStream) -> Self {
+            let mut tokens = input.clone().into_iter();
+            let span = tokens
+                .by_ref()
+                .find(|token| {
+                    matches!(token, TokenTree::Ident(ident)
+                        if ["struct", "enum", "union"].contains(&ident.to_string().as_str()))
+                })
+                .and_then(|_| tokens.next())
+                .map(|name| name.span());
+
+            Self {
+                note,
+                name,
+                span,
+                expression: false,
+            }
+        }
+
+        /// Adds the reference to the output, where its position allows: before items and
+        /// statements, at the start of the first function body, or along with an expression in
+        /// a block. Output whose position can't be known from its tokens is left unchanged.
+        pub fn apply(self, output: TokenStream) -> TokenStream {
+            let reference = format!(
+                "const _: () = {{ #[deprecated(note = {note})] macro_rules! {name} {{ () => {{}} }} \
+                 {name}!(); }};",
+                note = Literal::string(self.note),
+                name = self.name,
+            );
+            let reference = reference
+                .parse::<TokenStream>()
+                .expect("the deprecation reference is valid");
+            let reference = match self.span {
+                Some(span) => respan(reference, span),
+                None => reference,
+            };
+
+            let mut tokens = output.into_iter().collect::<Vec<_>>();
+            let position = match self.span {
+                // derives expand to items only
+                Some(_) => Position::Items,
+                None => position(&tokens, self.expression),
+            };
+
+            match position {
+                Position::Items => reference.into_iter().chain(tokens).collect(),
+                Position::Body(index) => {
+                    let TokenTree::Group(body) = &tokens[index] else {
+                        unreachable!("function bodies are groups");
+                    };
+                    let mut statements = body.stream().into_iter().collect::<Vec<_>>();
+                    // inner attributes have to stay first
+                    let mut start = 0;
+                    while is_punct(statements.get(start), '#')
+                        && is_punct(statements.get(start + 1), '!')
+                    {
+                        start += 3;
+                    }
+                    let start = start.min(statements.len());
+                    statements.splice(start..start, reference);
+
+                    let mut group = Group::new(Delimiter::Brace, statements.into_iter().collect());
+                    group.set_span(body.span());
+                    tokens[index] = group.into();
+                    tokens.into_iter().collect()
+                },
+                Position::Expression => {
+                    let block = Group::new(
+                        Delimiter::Brace,
+                        reference.into_iter().chain(tokens).collect(),
+                    );
+                    TokenTree::from(block).into()
+                },
+                Position::Unknown => tokens.into_iter().collect(),
+            }
+        }
+    }
+
+    /// Where the reference of a [`Deprecation`] can go in the output of a macro.
+    #[derive(Debug, PartialEq)]
+    enum Position {
+        /// Module items or statements, which a `const _` item can precede.
+        Items,
+        /// Functions, which could be associated items, but whose first body (at the index) can
+        /// start with an item in any case.
+        Body(usize),
+        /// An expression, which a block can wrap.
+        Expression,
+        /// Nothing, a macro call, associated constants or types or functions without a body,
+        /// which could be in any position.
+        Unknown,
+    }
+
+    /// Judges the position of the output by the items (or statements) it consists of. An item
+    /// starts the output and follows any top-level `;` or braced group, and is recognized by
+    /// its keyword, past its outer attributes, visibility and qualifiers.
+    fn position(tokens: &[TokenTree], expression: bool) -> Position {
+        let mut body = None;
+        let mut associated = false;
+        let mut statements = false;
+        let mut macro_call = false;
+
+        let mut start = Some(0);
+        for index in 0..tokens.len() {
+            if start == Some(index) {
+                let keyword = skip_prefix(tokens, index);
+                match ident_at(tokens, keyword).as_deref() {
+                    Some(
+                        "struct" | "enum" | "trait" | "impl" | "mod" | "use" | "static"
+                        | "macro_rules" | "let",
+                    ) => return Position::Items,
+                    Some("union") if ident_at(tokens, keyword + 1).is_some() => {
+                        return Position::Items
+                    },
+                    Some("extern") if is_extern_item(tokens, keyword) => return Position::Items,
+                    Some("fn") => {
+                        associated = true;
+                        if body.is_none() {
+                            body = tokens[keyword..]
+                                .iter()
+                                .take_while(|token| !is_punct(Some(token), ';'))
+                                .position(|token| {
+                                    matches!(token, TokenTree::Group(group)
+                                        if group.delimiter() == Delimiter::Brace)
+                                })
+                                .map(|offset| keyword + offset);
+                        }
+                    },
+                    Some("const" | "type") => associated = true,
+                    _ => macro_call |= is_macro_call(tokens, keyword),
+                }
+            }
+
+            match &tokens[index] {
+                TokenTree::Punct(punct) if punct.as_char() == ';' => {
+                    statements = true;
+                    start = Some(index + 1);
+                },
+                TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+                    start = Some(index + 1);
+                },
+                _ => {},
+            }
+        }
+
+        match body {
+            Some(index) => Position::Body(index),
+            None if associated || macro_call || tokens.is_empty() => Position::Unknown,
+            None if statements => Position::Items,
+            None if expression => Position::Expression,
+            None => Position::Unknown,
+        }
+    }
+
+    /// The index of the keyword of the item starting at `index`, past its outer attributes,
+    /// visibility and qualifiers (`const fn` and `unsafe impl` included).
+    fn skip_prefix(tokens: &[TokenTree], mut index: usize) -> usize {
+        loop {
+            match ident_at(tokens, index).as_deref() {
+                _ if is_punct(tokens.get(index), '#') => index += 2,
+                Some("pub") => {
+                    index += 1;
+                    if matches!(tokens.get(index), Some(TokenTree::Group(group))
+                        if group.delimiter() == Delimiter::Parenthesis)
+                    {
+                        index += 1;
+                    }
+                },
+                Some("unsafe" | "async" | "default" | "safe") => index += 1,
+                Some("const")
+                    if matches!(
+                        ident_at(tokens, index + 1).as_deref(),
+                        Some("fn" | "unsafe" | "async" | "extern")
+                    ) =>
+                {
+                    index += 1
+                },
+                Some("extern")
+                    if matches!(tokens.get(index + 1), Some(TokenTree::Literal(_)))
+                        && ident_at(tokens, index + 2).is_some() =>
+                {
+                    index += 2
+                },
+                _ => return index,
+            }
+        }
+    }
+
+    /// Whether the `extern` at `index` starts an `extern crate` or an `extern ["abi"] { .. }`
+    /// block, rather than qualifying a function.
+    fn is_extern_item(tokens: &[TokenTree], mut index: usize) -> bool {
+        if ident_at(tokens, index + 1).as_deref() == Some("crate") {
+            return true;
+        }
+        if matches!(tokens.get(index + 1), Some(TokenTree::Literal(_))) {
+            index += 1;
+        }
+        matches!(tokens.get(index + 1), Some(TokenTree::Group(group))
+            if group.delimiter() == Delimiter::Brace)
+    }
+
+    /// Whether the tokens at `index` are a macro call that could expand to items, such as
+    /// `thread_local! { .. }` or `include!(..);`. Others (e.g. `format!(..)`) are expressions.
+    fn is_macro_call(tokens: &[TokenTree], mut index: usize) -> bool {
+        while ident_at(tokens, index).is_some() || is_punct(tokens.get(index), ':') {
+            index += 1;
+        }
+        if !is_punct(tokens.get(index), '!') {
+            return false;
+        }
+        match tokens.get(index + 1) {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => true,
+            Some(TokenTree::Group(_)) => is_punct(tokens.get(index + 2), ';'),
+            _ => false,
+        }
+    }
+
+    fn ident_at(tokens: &[TokenTree], index: usize) -> Option<String> {
+        match tokens.get(index) {
+            Some(TokenTree::Ident(ident)) => Some(ident.to_string()),
+            _ => None,
+        }
+    }
+
+    fn is_punct(token: Option<&TokenTree>, char: char) -> bool {
+        matches!(token, Some(TokenTree::Punct(punct)) if punct.as_char() == char)
+    }
+
+    fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+        tokens
+            .into_iter()
+            .map(|mut token| {
+                if let TokenTree::Group(group) = &token {
+                    token = Group::new(group.delimiter(), respan(group.stream(), span)).into();
+                }
+                token.set_span(span);
+                token
+            })
+            .collect()
+    }
+
+    /// Checks the signature of an implementation against the one its kind and markers expect,
+    /// as it's called, so that a mismatch is reported as such (naming the expected signature)
+    /// rather than only as a type error somewhere in the generated entry point. The call has the
+    /// expected type either way, so that a mismatch isn't also reported by the code around it.
+    pub mod signature {
+        use core::marker::PhantomData;
+
+        /// The arguments (as a tuple) and output of a function pointer type.
+        pub trait Pointer {
+            type Arguments;
+            type Output;
+        }
+
+        impl<O> Pointer for fn() -> O {
+            type Arguments = ();
+            type Output = O;
+        }
+
+        impl<A, O> Pointer for fn(A) -> O {
+            type Arguments = (A,);
+            type Output = O;
+        }
+
+        impl<A, B, O> Pointer for fn(A, B) -> O {
+            type Arguments = (A, B);
+            type Output = O;
+        }
+
+        impl<A, B, C, O> Pointer for fn(A, B, C) -> O {
+            type Arguments = (A, B, C);
+            type Output = O;
+        }
+
+        /// Gives the function pointer type of a function, by its arguments, and calls it. The
+        /// output is projected rather than inferred, so that it's known by the time the pointer
+        /// is compared with the expected one.
+        #[diagnostic::on_unimplemented(
+            message = "implementations must be functions of up to three arguments, found `{Self}`"
+        )]
+        pub trait Signature<Arguments> {
+            type Pointer: Pointer<Arguments = Arguments>;
+
+            fn invoke(self, arguments: Arguments) -> <Self::Pointer as Pointer>::Output;
+        }
+
+        impl<F: Fn() -> O, O> Signature<()> for F {
+            type Pointer = fn() -> F::Output;
+
+            fn invoke(self, (): ()) -> F::Output {
+                self()
+            }
+        }
+
+        impl<F: Fn(A) -> O, A, O> Signature<(A,)> for F {
+            type Pointer = fn(A) -> F::Output;
+
+            fn invoke(self, (a,): (A,)) -> F::Output {
+                self(a)
+            }
+        }
+
+        impl<F: Fn(A, B) -> O, A, B, O> Signature<(A, B)> for F {
+            type Pointer = fn(A, B) -> F::Output;
+
+            fn invoke(self, (a, b): (A, B)) -> F::Output {
+                self(a, b)
+            }
+        }
+
+        impl<F: Fn(A, B, C) -> O, A, B, C, O> Signature<(A, B, C)> for F {
+            type Pointer = fn(A, B, C) -> F::Output;
+
+            fn invoke(self, (a, b, c): (A, B, C)) -> F::Output {
+                self(a, b, c)
+            }
+        }
+
+        /// Implemented by a pointer type for itself only, passing the arguments of the expected
+        /// one on, and its output back, once they're known to be the same.
+        pub trait Same<Expected: Pointer>: Pointer {
+            fn arguments(arguments: Expected::Arguments) -> Self::Arguments;
+
+            fn output(output: Self::Output) -> Expected::Output;
+        }
+
+        impl<T: Pointer> Same<T> for T {
+            fn arguments(arguments: T::Arguments) -> T::Arguments {
+                arguments
+            }
+
+            fn output(output: T::Output) -> T::Output {
+                output
+            }
+        }
+
+        #[diagnostic::on_unimplemented(
+            message = "function-like implementations must be `{Expected}`",
+            label = "expected `{Expected}`, found `{Self}`",
+            note = "implementations marked `pm2` (or parsing their input) use \
+                    `proc_macro2::TokenStream`s, the others `proc_macro::TokenStream`s"
+        )]
+        pub trait Function<Expected: Pointer>: Same<Expected> {}
+
+        impl<T: Pointer> Function<T> for T {}
+
+        #[diagnostic::on_unimplemented(
+            message = "attribute implementations must be `{Expected}`",
+            label = "expected `{Expected}`, found `{Self}`",
+            note = "implementations marked `pm2` (or parsing their input) use \
+                    `proc_macro2::TokenStream`s, the others `proc_macro::TokenStream`s"
+        )]
+        pub trait Attribute<Expected: Pointer>: Same<Expected> {}
+
+        impl<T: Pointer> Attribute<T> for T {}
+
+        #[diagnostic::on_unimplemented(
+            message = "derive implementations must be `{Expected}`",
+            label = "expected `{Expected}`, found `{Self}`",
+            note = "implementations marked `pm2` (or parsing their input) use \
+                    `proc_macro2::TokenStream`s, the others `proc_macro::TokenStream`s"
+        )]
+        pub trait Derive<Expected: Pointer>: Same<Expected> {}
+
+        impl<T: Pointer> Derive<T> for T {}
+
+        /// The expected function pointer type, known before the implementation is looked at.
+        pub struct Expected<E>(PhantomData<E>);
+
+        impl<E: Pointer> Expected<E> {
+            pub fn function<F: Signature<A>, A>(
+                implementation: F,
+                arguments: E::Arguments,
+            ) -> E::Output
+            where
+                F::Pointer: Function<E>,
+            {
+                invoke::<E, F, A>(implementation, arguments)
+            }
+
+            pub fn attribute<F: Signature<A>, A>(
+                implementation: F,
+                arguments: E::Arguments,
+            ) -> E::Output
+            where
+                F::Pointer: Attribute<E>,
+            {
+                invoke::<E, F, A>(implementation, arguments)
+            }
+
+            pub fn derive<F: Signature<A>, A>(
+                implementation: F,
+                arguments: E::Arguments,
+            ) -> E::Output
+            where
+                F::Pointer: Derive<E>,
+            {
+                invoke::<E, F, A>(implementation, arguments)
+            }
+        }
+
+        fn invoke<E: Pointer, F: Signature<A>, A>(
+            implementation: F,
+            arguments: E::Arguments,
+        ) -> E::Output
+        where
+            F::Pointer: Same<E>,
+        {
+            let arguments = <F::Pointer as Same<E>>::arguments(arguments);
+            <F::Pointer as Same<E>>::output(implementation.invoke(arguments))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::process::Command;